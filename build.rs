@@ -8,6 +8,7 @@ fn main() {
     println!("cargo:rustc-link-search={}", libuiohook_dir.display());
     println!("cargo:rustc-link-lib=uiohook");
     println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo:rerun-if-changed=src/logger_bridge.c");
 
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
 
@@ -15,7 +16,8 @@ fn main() {
     build
         .include(&libuiohook_dir.join("include"))
         .include(&libuiohook_dir.join("src"))
-        .file(libuiohook_dir.join("src/logger.c"));
+        .file(libuiohook_dir.join("src/logger.c"))
+        .file(root.join("src/logger_bridge.c"));
 
     match target_os.as_str() {
         "linux" => {
@@ -66,6 +68,7 @@ fn main() {
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .rustified_enum("_event_type")
         .rustified_enum("mouse_button")
+        .rustified_enum("_log_level")
         .derive_debug(true)
         .derive_default(true)
         .derive_eq(true)
@@ -42,11 +42,11 @@ impl DemoEventHandler {
                     _ => unreachable!(),
                 };
 
-                let key_info = format!("{:?}", keyboard_event.key_code);
+                let key_info = keyboard_event.key_code.to_string();
 
                 println!(
                     "{} | {:<17} | Code: {:<5} | Raw: {:<5}",
-                    event_type, key_info, keyboard_event.key_code as u16, keyboard_event.raw_code
+                    event_type, key_info, u32::from(keyboard_event.key_code), keyboard_event.raw_code
                 );
             }
             KeyboardEventType::Typed => {
@@ -58,8 +58,8 @@ impl DemoEventHandler {
                     };
 
                     println!(
-                        "{} | {:<17} | Code: {:<5} | Raw: {:<5}",
-                        format!("{:<8}", "TYPED"),
+                        "{:<8} | {:<17} | Code: {:<5} | Raw: {:<5}",
+                        "TYPED",
                         char_display,
                         ch as u32,
                         keyboard_event.raw_code
@@ -101,7 +101,7 @@ impl DemoEventHandler {
             "Amount: {:<4} | Rotation: {:<4} | Direction: {:<9}",
             wheel_event.amount,
             wheel_event.rotation,
-            if wheel_event.direction == uiohook_rs::hook::wheel::WHEEL_VERTICAL_DIRECTION {
+            if wheel_event.is_vertical() {
                 "Vertical"
             } else {
                 "Horizontal"
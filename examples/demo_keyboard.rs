@@ -21,13 +21,13 @@ impl DemoKeyboardHandler {
                     _ => unreachable!(),
                 };
 
-                let key_info = format!("{:?}", keyboard_event.key_code);
+                let key_info = keyboard_event.key_code.to_string();
 
                 println!(
                     "{:<8} | {:<17} | Code: {:<5} | Raw: {:<5}",
                     event_type,
                     key_info,
-                    keyboard_event.key_code as u16,
+                    u32::from(keyboard_event.key_code),
                     keyboard_event.raw_code
                 );
             }
@@ -69,6 +69,7 @@ fn main() {
         unsafe {
             core_foundation::runloop::CFRunLoopRun();
         }
+        println!("Exiting...");
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -76,6 +77,4 @@ fn main() {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
     }
-
-    println!("Exiting...");
 }
@@ -54,6 +54,7 @@ fn main() {
         unsafe {
             core_foundation::runloop::CFRunLoopRun();
         }
+        println!("Exiting...");
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -61,6 +62,4 @@ fn main() {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
     }
-
-    println!("Exiting...");
 }
@@ -19,7 +19,7 @@ impl DemoWheelHandler {
             "Amount: {:<4} | Rotation: {:<4} | Direction: {:<9}",
             wheel_event.amount,
             wheel_event.rotation,
-            if wheel_event.direction == uiohook_rs::hook::wheel::WHEEL_VERTICAL_DIRECTION {
+            if wheel_event.is_vertical() {
                 "Vertical"
             } else {
                 "Horizontal"
@@ -53,6 +53,7 @@ fn main() {
         unsafe {
             core_foundation::runloop::CFRunLoopRun();
         }
+        println!("Exiting...");
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -60,6 +61,4 @@ fn main() {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
     }
-
-    println!("Exiting...");
 }
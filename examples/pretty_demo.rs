@@ -43,11 +43,11 @@ impl DemoEventHandler {
                     _ => unreachable!(),
                 };
 
-                let key_info = format!("{:?}", keyboard_event.key_code).yellow();
+                let key_info = keyboard_event.key_code.to_string().yellow();
 
                 println!(
                     "{} | {:<17} | Code: {:<5} | Raw: {:<5}",
-                    event_type, key_info, keyboard_event.key_code as u16, keyboard_event.raw_code
+                    event_type, key_info, u32::from(keyboard_event.key_code), keyboard_event.raw_code
                 );
             }
             KeyboardEventType::Typed => {
@@ -102,7 +102,7 @@ impl DemoEventHandler {
             "Amount: {:<4} | Rotation: {:<4} | Direction: {:<9}",
             wheel_event.amount,
             wheel_event.rotation,
-            if wheel_event.direction == uiohook_rs::hook::wheel::WHEEL_VERTICAL_DIRECTION {
+            if wheel_event.is_vertical() {
                 "Vertical"
             } else {
                 "Horizontal"
@@ -0,0 +1,486 @@
+//! Click counting computed in Rust, identical on every platform.
+//!
+//! libuiohook reports `clicks` as the platform computes it: some platforms
+//! reset the count early, some count across different buttons, and drags
+//! report no button at all. A [`ClickTracker`] ignores the native counts and
+//! derives them from press timing and distance instead. A press continues a
+//! multi-click when it uses the same button, follows the previous press
+//! within the multi-click time and lands within the slop radius of it. A
+//! release completes a click unless the pointer strayed outside the slop
+//! radius while the button was held.
+//!
+//! [`ClickLayer`] wraps an [`EventHandler`] so it receives the normalized
+//! stream: native `Clicked` events are dropped, `Pressed` and `Released`
+//! events carry the tracked count, and a `Clicked` event is delivered after
+//! every release that completes a click.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use uiohook_rs::click::{ClickLayer, ClickTracker};
+//! use uiohook_rs::hook::mouse::MouseEventType;
+//! use uiohook_rs::{EventHandler, Uiohook, UiohookEvent};
+//!
+//! struct MyHandler;
+//!
+//! impl EventHandler for MyHandler {
+//!     fn handle_event(&self, event: &UiohookEvent) {
+//!         if let UiohookEvent::Mouse(me) = event {
+//!             if me.event_type == MouseEventType::Clicked && me.clicks == 2 {
+//!                 println!("Double click with {:?}", me.button);
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let tracker = ClickTracker::new(uiohook_rs::get_multi_click_time().unwrap_or(uiohook_rs::DEFAULT_MULTI_CLICK_TIME));
+//! let uiohook = Uiohook::new(ClickLayer::new(tracker, MyHandler));
+//! uiohook.run().expect("Failed to run uiohook");
+//! ```
+
+use crate::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+use crate::utils::DEFAULT_MULTI_CLICK_TIME;
+use crate::{EventHandler, EventMeta, Uiohook, UiohookEvent};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// The default slop radius, in pixels.
+pub const DEFAULT_SLOP: u16 = 4;
+
+/// A completed click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Click {
+    /// The button that was clicked.
+    pub button: MouseButton,
+    /// The position of the click within a multi-click: 1 for a single click,
+    /// 2 for a double click, and so on.
+    pub count: u16,
+    /// The x-coordinate of the release.
+    pub x: i16,
+    /// The y-coordinate of the release.
+    pub y: i16,
+    /// The timestamp and modifier state of the release.
+    pub meta: EventMeta,
+}
+
+impl From<&Click> for MouseEvent {
+    fn from(click: &Click) -> Self {
+        MouseEvent {
+            event_type: MouseEventType::Clicked,
+            button: click.button,
+            clicks: click.count,
+            x: click.x,
+            y: click.y,
+            meta: click.meta,
+        }
+    }
+}
+
+// The press that started or continued the current multi-click.
+struct LastPress {
+    button: MouseButton,
+    position: (i16, i16),
+    time: Duration,
+    count: u16,
+}
+
+// A button being held down.
+struct HeldButton {
+    position: (i16, i16),
+    count: u16,
+    // The pointer left the slop radius, so the release is not a click.
+    strayed: bool,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    last_press: Option<LastPress>,
+    held: HashMap<MouseButton, HeldButton>,
+}
+
+/// Computes click counts from mouse button presses and releases.
+pub struct ClickTracker {
+    multi_click_time: Duration,
+    slop: u16,
+    state: Mutex<TrackerState>,
+}
+
+impl ClickTracker {
+    /// Create a tracker with the given multi-click time and [`DEFAULT_SLOP`].
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_click_time` - The longest time between two presses of a
+    ///   multi-click, in milliseconds, as returned by
+    ///   [`get_multi_click_time`](crate::get_multi_click_time). Negative
+    ///   values are treated as zero.
+    pub fn new(multi_click_time: i64) -> Self {
+        ClickTracker {
+            multi_click_time: Duration::from_millis(multi_click_time.max(0) as u64),
+            slop: DEFAULT_SLOP,
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    /// Create a tracker using the multi-click time of the system `uiohook` runs on.
+    ///
+    /// Falls back to [`DEFAULT_MULTI_CLICK_TIME`] if the system does not report one.
+    pub fn from_system(uiohook: &Uiohook) -> Self {
+        Self::new(uiohook.multi_click_time().unwrap_or(DEFAULT_MULTI_CLICK_TIME))
+    }
+
+    /// Returns the tracker with its slop radius replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `slop` - How far, in pixels, the pointer may move between the presses
+    ///   of a multi-click, and while a button is held, before it stops
+    ///   counting as a click.
+    pub fn with_slop(mut self, slop: u16) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    /// Returns the longest time between two presses of a multi-click.
+    pub fn multi_click_time(&self) -> Duration {
+        self.multi_click_time
+    }
+
+    /// Returns the slop radius in pixels.
+    pub fn slop(&self) -> u16 {
+        self.slop
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TrackerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn within_slop(&self, a: (i16, i16), b: (i16, i16)) -> bool {
+        let dx = f64::from(a.0) - f64::from(b.0);
+        let dy = f64::from(a.1) - f64::from(b.1);
+        dx.hypot(dy) <= f64::from(self.slop)
+    }
+
+    /// Feed a mouse event to the tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The mouse event. Its timestamp, not the wall clock, is used
+    ///   for timing, and native `Clicked` events are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The click completed by a release, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::click::ClickTracker;
+    /// use uiohook_rs::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+    /// use uiohook_rs::EventMeta;
+    /// use std::time::Duration;
+    ///
+    /// let tracker = ClickTracker::new(500);
+    /// let event = |event_type, ms| MouseEvent {
+    ///     event_type,
+    ///     button: MouseButton::Button1,
+    ///     clicks: 0,
+    ///     x: 10,
+    ///     y: 10,
+    ///     meta: EventMeta::new(Duration::from_millis(ms), Default::default()),
+    /// };
+    ///
+    /// tracker.feed(&event(MouseEventType::Pressed, 0));
+    /// assert_eq!(tracker.feed(&event(MouseEventType::Released, 80)).map(|c| c.count), Some(1));
+    /// tracker.feed(&event(MouseEventType::Pressed, 200));
+    /// assert_eq!(tracker.feed(&event(MouseEventType::Released, 280)).map(|c| c.count), Some(2));
+    /// ```
+    pub fn feed(&self, event: &MouseEvent) -> Option<Click> {
+        self.track(event).1
+    }
+
+    // Returns the tracked count for presses and releases, and the completed click.
+    fn track(&self, event: &MouseEvent) -> (Option<u16>, Option<Click>) {
+        let mut state = self.lock();
+        let position = (event.x, event.y);
+        match event.event_type {
+            MouseEventType::Pressed => {
+                let count = match &state.last_press {
+                    Some(last)
+                        if last.button == event.button
+                            && event.meta.time.saturating_sub(last.time) <= self.multi_click_time
+                            && self.within_slop(last.position, position) =>
+                    {
+                        last.count.saturating_add(1)
+                    }
+                    _ => 1,
+                };
+                state.last_press = Some(LastPress {
+                    button: event.button,
+                    position,
+                    time: event.meta.time,
+                    count,
+                });
+                state.held.insert(
+                    event.button,
+                    HeldButton {
+                        position,
+                        count,
+                        strayed: false,
+                    },
+                );
+                (Some(count), None)
+            }
+            MouseEventType::Moved | MouseEventType::Dragged => {
+                for held in state.held.values_mut() {
+                    held.strayed |= !self.within_slop(held.position, position);
+                }
+                (None, None)
+            }
+            MouseEventType::Released => {
+                let Some(held) = state.held.remove(&event.button) else {
+                    return (None, None);
+                };
+                if held.strayed || !self.within_slop(held.position, position) {
+                    // A drag ends the multi-click.
+                    state.last_press = None;
+                    return (Some(held.count), None);
+                }
+                let click = Click {
+                    button: event.button,
+                    count: held.count,
+                    x: event.x,
+                    y: event.y,
+                    meta: event.meta,
+                };
+                (Some(held.count), Some(click))
+            }
+            MouseEventType::Clicked => (None, None),
+        }
+    }
+
+    /// Rewrite an event into the normalized stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Any captured event.
+    ///
+    /// # Returns
+    ///
+    /// The events to deliver in its place: nothing for a native `Clicked`
+    /// event, a press or release carrying the tracked count, followed by a
+    /// `Clicked` event if the release completed a click, or the event itself.
+    pub fn normalize(&self, event: &UiohookEvent) -> Vec<UiohookEvent> {
+        let UiohookEvent::Mouse(me) = event else {
+            if matches!(event, UiohookEvent::HookDisabled) {
+                // Releases are not delivered while the hook is down.
+                *self.lock() = TrackerState::default();
+            }
+            return vec![event.clone()];
+        };
+        if me.event_type == MouseEventType::Clicked {
+            return Vec::new();
+        }
+
+        let (count, click) = self.track(me);
+        let mut me = me.clone();
+        if let Some(count) = count {
+            me.clicks = count;
+        }
+        let mut events = vec![UiohookEvent::Mouse(me)];
+        events.extend(click.map(|click| UiohookEvent::Mouse(MouseEvent::from(&click))));
+        events
+    }
+}
+
+/// An [`EventHandler`] that delivers the normalized click stream of a
+/// [`ClickTracker`] to another handler.
+pub struct ClickLayer<H> {
+    tracker: ClickTracker,
+    handler: H,
+}
+
+impl<H: EventHandler> ClickLayer<H> {
+    /// Wrap `handler` so it receives events normalized by `tracker`.
+    pub fn new(tracker: ClickTracker, handler: H) -> Self {
+        ClickLayer { tracker, handler }
+    }
+
+    /// Returns the tracker counting the clicks.
+    pub fn tracker(&self) -> &ClickTracker {
+        &self.tracker
+    }
+}
+
+impl<H: EventHandler> EventHandler for ClickLayer<H> {
+    fn handle_event(&self, event: &UiohookEvent) {
+        for event in self.tracker.normalize(event) {
+            self.handler.handle_event(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::backend::{HookBackend, MockBackend};
+    use crate::SystemProperties;
+    use std::sync::Arc;
+
+    fn mouse_event(event_type: MouseEventType, button: MouseButton, x: i16, y: i16, ms: u64) -> MouseEvent {
+        MouseEvent {
+            event_type,
+            button,
+            clicks: 0,
+            x,
+            y,
+            meta: EventMeta::new(Duration::from_millis(ms), Default::default()),
+        }
+    }
+
+    // Feeds a press at `ms` and a release 50ms later, returning the click count.
+    fn click(tracker: &ClickTracker, button: MouseButton, x: i16, y: i16, ms: u64) -> Option<u16> {
+        assert_eq!(tracker.feed(&mouse_event(MouseEventType::Pressed, button, x, y, ms)), None);
+        tracker
+            .feed(&mouse_event(MouseEventType::Released, button, x, y, ms + 50))
+            .map(|click| click.count)
+    }
+
+    #[test]
+    fn test_multi_clicks() {
+        let tracker = ClickTracker::new(400);
+        let left = MouseButton::Button1;
+
+        assert_eq!(click(&tracker, left, 100, 100, 0), Some(1));
+        assert_eq!(click(&tracker, left, 101, 99, 300), Some(2));
+        assert_eq!(click(&tracker, left, 103, 100, 700), Some(3));
+        // Too slow: the chain starts over.
+        assert_eq!(click(&tracker, left, 103, 100, 1101), Some(1));
+        assert_eq!(click(&tracker, left, 103, 100, 1200), Some(2));
+    }
+
+    #[test]
+    fn test_slop_and_buttons_break_chains() {
+        let tracker = ClickTracker::new(500).with_slop(5);
+        let (left, right) = (MouseButton::Button1, MouseButton::Button2);
+
+        assert_eq!(click(&tracker, left, 0, 0, 0), Some(1));
+        assert_eq!(click(&tracker, left, 3, 4, 100), Some(2));
+        assert_eq!(click(&tracker, left, 9, 4, 200), Some(1));
+        assert_eq!(click(&tracker, right, 9, 4, 300), Some(1));
+        assert_eq!(click(&tracker, left, 9, 4, 400), Some(1));
+    }
+
+    #[test]
+    fn test_drags_are_not_clicks() {
+        let tracker = ClickTracker::new(500);
+        let left = MouseButton::Button1;
+
+        tracker.feed(&mouse_event(MouseEventType::Pressed, left, 0, 0, 0));
+        tracker.feed(&mouse_event(MouseEventType::Dragged, MouseButton::NoButton, 40, 0, 20));
+        // Coming back to the press position does not make it a click.
+        tracker.feed(&mouse_event(MouseEventType::Dragged, MouseButton::NoButton, 1, 0, 40));
+        assert_eq!(tracker.feed(&mouse_event(MouseEventType::Released, left, 1, 0, 60)), None);
+
+        // The drag also ended the multi-click.
+        assert_eq!(click(&tracker, left, 0, 0, 100), Some(1));
+
+        // Releases without a press are ignored.
+        assert_eq!(tracker.feed(&mouse_event(MouseEventType::Released, left, 0, 0, 700)), None);
+    }
+
+    #[test]
+    fn test_normalized_stream() {
+        let tracker = ClickTracker::new(500);
+        let left = MouseButton::Button1;
+        let normalize = |event_type, ms| {
+            let mut event = mouse_event(event_type, left, 7, 8, ms);
+            // Native counts are replaced whatever they were.
+            event.clicks = 9;
+            tracker.normalize(&UiohookEvent::Mouse(event))
+        };
+        let summary = |events: Vec<UiohookEvent>| -> Vec<(MouseEventType, u16)> {
+            events
+                .into_iter()
+                .map(|event| match event {
+                    UiohookEvent::Mouse(me) => (me.event_type, me.clicks),
+                    other => panic!("Unexpected event {:?}", other),
+                })
+                .collect()
+        };
+
+        assert_eq!(summary(normalize(MouseEventType::Pressed, 0)), vec![(MouseEventType::Pressed, 1)]);
+        assert_eq!(
+            summary(normalize(MouseEventType::Released, 50)),
+            vec![(MouseEventType::Released, 1), (MouseEventType::Clicked, 1)]
+        );
+        assert!(normalize(MouseEventType::Clicked, 50).is_empty());
+        assert_eq!(summary(normalize(MouseEventType::Pressed, 200)), vec![(MouseEventType::Pressed, 2)]);
+        assert_eq!(
+            summary(normalize(MouseEventType::Released, 250)),
+            vec![(MouseEventType::Released, 2), (MouseEventType::Clicked, 2)]
+        );
+        assert_eq!(tracker.normalize(&UiohookEvent::HookEnabled).len(), 1);
+    }
+
+    #[test]
+    fn test_from_system() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+
+        // Unrelated settings being unavailable does not matter.
+        backend.set_system_properties(SystemProperties {
+            multi_click_time: Some(300),
+            ..SystemProperties::default()
+        });
+        assert_eq!(ClickTracker::from_system(&hook).multi_click_time(), Duration::from_millis(300));
+
+        backend.set_system_properties(SystemProperties::default());
+        assert_eq!(
+            ClickTracker::from_system(&hook).multi_click_time(),
+            Duration::from_millis(DEFAULT_MULTI_CLICK_TIME as u64)
+        );
+    }
+
+    #[test]
+    fn test_click_layer() {
+        struct Record(Arc<Mutex<Vec<MouseEvent>>>);
+
+        impl EventHandler for Record {
+            fn handle_event(&self, event: &UiohookEvent) {
+                if let UiohookEvent::Mouse(me) = event {
+                    self.0.lock().unwrap().push(me.clone());
+                }
+            }
+        }
+
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        let tracker = ClickTracker::from_system(&hook).with_slop(2);
+        assert_eq!(tracker.multi_click_time(), Duration::from_millis(500));
+        assert_eq!(tracker.slop(), 2);
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        backend.dispatcher().subscribe(ClickLayer::new(tracker, Record(events.clone())));
+        for (event_type, ms) in [
+            (MouseEventType::Pressed, 0),
+            (MouseEventType::Released, 40),
+            (MouseEventType::Clicked, 40),
+            (MouseEventType::Pressed, 120),
+            (MouseEventType::Released, 160),
+            (MouseEventType::Clicked, 160),
+        ] {
+            backend.inject(&UiohookEvent::Mouse(mouse_event(event_type, MouseButton::Button3, 5, 5, ms)));
+        }
+
+        let clicks: Vec<u16> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|me| me.event_type == MouseEventType::Clicked)
+            .map(|me| me.clicks)
+            .collect();
+        assert_eq!(clicks, vec![1, 2]);
+    }
+}
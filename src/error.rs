@@ -79,6 +79,18 @@ pub enum UiohookError {
     #[error("The hook is not initialized")]
     NotInitialized,
 
+    /// An event handler panicked and the panic policy stopped the hook.
+    #[error("An event handler panicked")]
+    HandlerPanicked,
+
+    /// An argument was out of range or otherwise invalid.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(&'static str),
+
+    /// The specified key code is not recognized.
+    #[error("Unknown key code: {0}")]
+    UnknownKeyCode(u32),
+
     /// The specified mouse button is not recognized.
     #[error("Unknown mouse button: {0}")]
     UnknownMouseButton(u32),
@@ -140,6 +152,9 @@ mod tests {
         assert_eq!(UiohookError::Failure.to_string(), "Operation failed");
         assert_eq!(UiohookError::OutOfMemory.to_string(), "Out of memory");
         assert_eq!(UiohookError::XOpenDisplay.to_string(), "X11 failed to open display");
+        assert_eq!(UiohookError::HandlerPanicked.to_string(), "An event handler panicked");
+        assert_eq!(UiohookError::InvalidArgument("amount").to_string(), "Invalid argument: amount");
+        assert_eq!(UiohookError::UnknownKeyCode(0xE001).to_string(), "Unknown key code: 57345");
         assert_eq!(UiohookError::Unknown(999).to_string(), "Unknown error: 999");
     }
 }
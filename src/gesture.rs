@@ -0,0 +1,570 @@
+//! Mouse stroke gestures, such as dragging left then up with the right button.
+//!
+//! A [`GestureRecognizer`] watches one mouse button. While it is held, the
+//! pointer path is split into straight segments of at least a minimum length,
+//! each quantized to one of four or eight [`Direction`]s. When the button is
+//! released, the directions are compared with the registered gestures and a
+//! [`GestureEvent`] is produced for the one that matches exactly.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use uiohook_rs::gesture::GestureRecognizer;
+//! use uiohook_rs::hook::mouse::MouseButton;
+//! use uiohook_rs::Uiohook;
+//!
+//! let gestures = GestureRecognizer::new(MouseButton::Button2);
+//! gestures.register("back", "left up").expect("Failed to register gesture");
+//! gestures.register("forward", "right up").expect("Failed to register gesture");
+//! gestures.on_gesture(|gesture| println!("Gesture: {}", gesture.name));
+//!
+//! let uiohook = Uiohook::new(gestures.clone());
+//! uiohook.run().expect("Failed to run uiohook");
+//! ```
+
+use crate::error::{Result, UiohookError};
+use crate::hook::keyboard::normalize_key_name;
+use crate::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+use crate::{EventHandler, UiohookEvent};
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The default minimum length of a gesture segment, in pixels.
+pub const DEFAULT_MIN_SEGMENT_LENGTH: u16 = 30;
+
+/// The direction of one straight segment of a gesture.
+///
+/// Directions are on screen, so `Up` is towards smaller y coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Towards the top of the screen.
+    Up,
+    /// Towards the bottom of the screen.
+    Down,
+    /// Towards the left of the screen.
+    Left,
+    /// Towards the right of the screen.
+    Right,
+    /// Towards the top left. Only produced with eight-way quantization.
+    UpLeft,
+    /// Towards the top right. Only produced with eight-way quantization.
+    UpRight,
+    /// Towards the bottom left. Only produced with eight-way quantization.
+    DownLeft,
+    /// Towards the bottom right. Only produced with eight-way quantization.
+    DownRight,
+}
+
+impl Direction {
+    /// Returns `true` for the four diagonal directions.
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::UpLeft | Direction::UpRight | Direction::DownLeft | Direction::DownRight
+        )
+    }
+
+    /// Quantizes a movement to the nearest direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `dx` - The horizontal distance moved, positive to the right.
+    /// * `dy` - The vertical distance moved, positive downwards.
+    /// * `quantization` - Whether diagonal directions may be returned.
+    ///
+    /// # Returns
+    ///
+    /// The direction, or `None` if the movement is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::gesture::{Direction, Quantization};
+    ///
+    /// assert_eq!(Direction::from_delta(10, -3, Quantization::Four), Some(Direction::Right));
+    /// assert_eq!(Direction::from_delta(10, -9, Quantization::Eight), Some(Direction::UpRight));
+    /// ```
+    pub fn from_delta(dx: i32, dy: i32, quantization: Quantization) -> Option<Direction> {
+        const FOUR: [Direction; 4] = [Direction::Right, Direction::Up, Direction::Left, Direction::Down];
+        const EIGHT: [Direction; 8] = [
+            Direction::Right,
+            Direction::UpRight,
+            Direction::Up,
+            Direction::UpLeft,
+            Direction::Left,
+            Direction::DownLeft,
+            Direction::Down,
+            Direction::DownRight,
+        ];
+
+        if dx == 0 && dy == 0 {
+            return None;
+        }
+        let directions: &[Direction] = match quantization {
+            Quantization::Four => &FOUR,
+            Quantization::Eight => &EIGHT,
+        };
+        // Screen y grows downwards, so flip it to get a counter-clockwise angle.
+        let angle = f64::from(-dy).atan2(f64::from(dx));
+        let sector = 2.0 * PI / directions.len() as f64;
+        let index = (angle / sector).round() as i64;
+        Some(directions[index.rem_euclid(directions.len() as i64) as usize])
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Direction::Up => "Up",
+            Direction::Down => "Down",
+            Direction::Left => "Left",
+            Direction::Right => "Right",
+            Direction::UpLeft => "Up-Left",
+            Direction::UpRight => "Up-Right",
+            Direction::DownLeft => "Down-Left",
+            Direction::DownRight => "Down-Right",
+        })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = UiohookError;
+
+    /// Parses a direction name such as `"up"`, `"Up-Left"` or the
+    /// abbreviations `"u"` and `"ul"`. Case, spaces, underscores and hyphens
+    /// are ignored.
+    fn from_str(s: &str) -> Result<Self> {
+        match normalize_key_name(s).as_str() {
+            "up" | "u" => Ok(Direction::Up),
+            "down" | "d" => Ok(Direction::Down),
+            "left" | "l" => Ok(Direction::Left),
+            "right" | "r" => Ok(Direction::Right),
+            "upleft" | "ul" => Ok(Direction::UpLeft),
+            "upright" | "ur" => Ok(Direction::UpRight),
+            "downleft" | "dl" => Ok(Direction::DownLeft),
+            "downright" | "dr" => Ok(Direction::DownRight),
+            _ => Err(UiohookError::InvalidArgument("unknown gesture direction")),
+        }
+    }
+}
+
+/// How many directions gesture segments are quantized to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Quantization {
+    /// Up, down, left and right.
+    #[default]
+    Four,
+    /// The four axis directions and the four diagonals.
+    Eight,
+}
+
+/// A recognized gesture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GestureEvent {
+    /// The name the gesture was registered under.
+    pub name: String,
+    /// The directions of the gesture's segments.
+    pub directions: Vec<Direction>,
+    /// The button held during the gesture.
+    pub button: MouseButton,
+    /// Where the button was pressed.
+    pub start: (i16, i16),
+    /// Where the button was released.
+    pub end: (i16, i16),
+}
+
+type GestureCallback = Arc<dyn Fn(&GestureEvent) + Send + Sync>;
+
+struct RecognizerState {
+    button: MouseButton,
+    min_segment_length: u16,
+    quantization: Quantization,
+    gestures: Vec<(String, Vec<Direction>)>,
+    on_gesture: Option<GestureCallback>,
+    stroke: Option<Stroke>,
+}
+
+// The path of the gesture in progress.
+struct Stroke {
+    start: (i16, i16),
+    // The end of the last completed segment.
+    anchor: (i16, i16),
+    directions: Vec<Direction>,
+}
+
+/// Recognizes named stroke gestures drawn while a mouse button is held.
+///
+/// Clones share the same gestures and state. The gesture callback runs on the
+/// thread that feeds the events, without the recognizer locked.
+#[derive(Clone)]
+pub struct GestureRecognizer {
+    state: Arc<Mutex<RecognizerState>>,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer for gestures drawn with `button` held, using
+    /// [`DEFAULT_MIN_SEGMENT_LENGTH`] and four-way quantization.
+    pub fn new(button: MouseButton) -> Self {
+        GestureRecognizer {
+            state: Arc::new(Mutex::new(RecognizerState {
+                button,
+                min_segment_length: DEFAULT_MIN_SEGMENT_LENGTH,
+                quantization: Quantization::Four,
+                gestures: Vec::new(),
+                on_gesture: None,
+                stroke: None,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RecognizerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set how far the pointer must move, in pixels, before a segment counts.
+    ///
+    /// Shorter movements are treated as jitter. A length of zero is treated as one.
+    pub fn set_min_segment_length(&self, length: u16) {
+        self.lock().min_segment_length = length.max(1);
+    }
+
+    /// Set how many directions segments are quantized to.
+    ///
+    /// Gestures with diagonal directions never match with four-way quantization.
+    pub fn set_quantization(&self, quantization: Quantization) {
+        self.lock().quantization = quantization;
+    }
+
+    /// Register a gesture from a list of direction names.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name reported in [`GestureEvent::name`]. Registering a
+    ///   name again replaces the earlier gesture.
+    /// * `directions` - Direction names separated by spaces or commas, e.g.
+    ///   `"left up"` or `"down, right"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if a direction cannot be parsed
+    /// (see [`GestureRecognizer::register_directions`] for other errors).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::gesture::{Direction, GestureRecognizer};
+    /// use uiohook_rs::hook::mouse::MouseButton;
+    ///
+    /// let gestures = GestureRecognizer::new(MouseButton::Button2);
+    /// gestures.register("back", "left up").expect("Failed to register gesture");
+    /// assert!(gestures.register("reload", "up-left").is_err());
+    /// assert!(gestures.register("close", "down, sideways").is_err());
+    /// ```
+    pub fn register(&self, name: &str, directions: &str) -> Result<()> {
+        let directions = directions
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|direction| !direction.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Direction>>>()?;
+        self.register_directions(name, &directions)
+    }
+
+    /// Register a gesture.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name reported in [`GestureEvent::name`]. Registering a
+    ///   name again replaces the earlier gesture.
+    /// * `directions` - The directions of the gesture's segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if `directions` is empty,
+    /// repeats a direction back to back (a straight line is one segment), or
+    /// uses diagonals while four-way quantization is selected.
+    pub fn register_directions(&self, name: &str, directions: &[Direction]) -> Result<()> {
+        if directions.is_empty() {
+            return Err(UiohookError::InvalidArgument("gesture has no directions"));
+        }
+        if directions.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(UiohookError::InvalidArgument("gesture repeats a direction"));
+        }
+
+        let mut state = self.lock();
+        if state.quantization == Quantization::Four && directions.iter().any(Direction::is_diagonal) {
+            return Err(UiohookError::InvalidArgument("diagonal gestures need eight-way quantization"));
+        }
+        state.gestures.retain(|(existing, _)| existing != name);
+        state.gestures.push((name.to_string(), directions.to_vec()));
+        Ok(())
+    }
+
+    /// Remove a registered gesture.
+    ///
+    /// Returns `true` if the gesture was registered.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut state = self.lock();
+        let before = state.gestures.len();
+        state.gestures.retain(|(existing, _)| existing != name);
+        state.gestures.len() != before
+    }
+
+    /// Set the callback told about every recognized gesture.
+    pub fn on_gesture<F>(&self, callback: F)
+    where
+        F: Fn(&GestureEvent) + Send + Sync + 'static,
+    {
+        self.lock().on_gesture = Some(Arc::new(callback));
+    }
+
+    /// Feed a mouse event to the recognizer.
+    ///
+    /// Drag and move events extend the gesture while its button is held;
+    /// releasing the button completes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The mouse event.
+    ///
+    /// # Returns
+    ///
+    /// The recognized gesture if the event completed a registered one.
+    pub fn feed(&self, event: &MouseEvent) -> Option<GestureEvent> {
+        let mut state = self.lock();
+        let position = (event.x, event.y);
+        let recognized = match event.event_type {
+            MouseEventType::Pressed if event.button == state.button => {
+                state.stroke = Some(Stroke {
+                    start: position,
+                    anchor: position,
+                    directions: Vec::new(),
+                });
+                None
+            }
+            // Drags report `NoButton` on some platforms, so any motion counts.
+            MouseEventType::Dragged | MouseEventType::Moved => {
+                let (min_segment_length, quantization) = (state.min_segment_length, state.quantization);
+                if let Some(stroke) = state.stroke.as_mut() {
+                    stroke.extend(position, min_segment_length, quantization);
+                }
+                None
+            }
+            MouseEventType::Released if event.button == state.button => {
+                let mut stroke = state.stroke.take()?;
+                let (min_segment_length, quantization) = (state.min_segment_length, state.quantization);
+                stroke.extend(position, min_segment_length, quantization);
+                state
+                    .gestures
+                    .iter()
+                    .find(|(_, directions)| *directions == stroke.directions)
+                    .map(|(name, _)| GestureEvent {
+                        name: name.clone(),
+                        directions: stroke.directions,
+                        button: state.button,
+                        start: stroke.start,
+                        end: position,
+                    })
+            }
+            _ => None,
+        };
+
+        let callback = state.on_gesture.clone();
+        drop(state);
+        if let (Some(callback), Some(gesture)) = (callback, &recognized) {
+            callback(gesture);
+        }
+        recognized
+    }
+}
+
+impl Stroke {
+    // Adds a segment once the pointer is far enough from the last anchor.
+    fn extend(&mut self, position: (i16, i16), min_segment_length: u16, quantization: Quantization) {
+        let dx = i32::from(position.0) - i32::from(self.anchor.0);
+        let dy = i32::from(position.1) - i32::from(self.anchor.1);
+        let length = f64::from(dx).hypot(f64::from(dy));
+        if length < f64::from(min_segment_length) {
+            return;
+        }
+        if let Some(direction) = Direction::from_delta(dx, dy, quantization) {
+            if self.directions.last() != Some(&direction) {
+                self.directions.push(direction);
+            }
+        }
+        self.anchor = position;
+    }
+}
+
+impl EventHandler for GestureRecognizer {
+    fn handle_event(&self, event: &UiohookEvent) {
+        match event {
+            UiohookEvent::Mouse(me) => {
+                self.feed(me);
+            }
+            // The release of a held button is lost while the hook is down.
+            UiohookEvent::HookDisabled => self.lock().stroke = None,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::backend::{HookBackend, MockBackend};
+    use crate::EventMeta;
+
+    fn mouse_event(event_type: MouseEventType, button: MouseButton, x: i16, y: i16) -> MouseEvent {
+        MouseEvent {
+            event_type,
+            button,
+            clicks: 0,
+            x,
+            y,
+            meta: EventMeta::default(),
+        }
+    }
+
+    // Feeds a press of `button` at the first point, drags through the rest
+    // and releases at the last one.
+    fn draw(gestures: &GestureRecognizer, button: MouseButton, points: &[(i16, i16)]) -> Option<GestureEvent> {
+        let (first, last) = (points[0], points[points.len() - 1]);
+        assert_eq!(gestures.feed(&mouse_event(MouseEventType::Pressed, button, first.0, first.1)), None);
+        for &(x, y) in &points[1..] {
+            assert_eq!(gestures.feed(&mouse_event(MouseEventType::Dragged, MouseButton::NoButton, x, y)), None);
+        }
+        gestures.feed(&mouse_event(MouseEventType::Released, button, last.0, last.1))
+    }
+
+    #[test]
+    fn test_quantization() {
+        use Direction::*;
+
+        let four = |dx, dy| Direction::from_delta(dx, dy, Quantization::Four);
+        assert_eq!(four(0, 0), None);
+        assert_eq!(four(5, 0), Some(Right));
+        assert_eq!(four(-5, 1), Some(Left));
+        assert_eq!(four(2, -7), Some(Up));
+        assert_eq!(four(-2, 7), Some(Down));
+        assert_eq!(four(10, -9), Some(Right));
+
+        let eight = |dx, dy| Direction::from_delta(dx, dy, Quantization::Eight);
+        assert_eq!(eight(10, -9), Some(UpRight));
+        assert_eq!(eight(-10, -9), Some(UpLeft));
+        assert_eq!(eight(-10, 9), Some(DownLeft));
+        assert_eq!(eight(10, 9), Some(DownRight));
+        assert_eq!(eight(10, 3), Some(Right));
+        assert_eq!(eight(-10, 1), Some(Left));
+    }
+
+    #[test]
+    fn test_direction_names() {
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+            Direction::UpLeft,
+            Direction::UpRight,
+            Direction::DownLeft,
+            Direction::DownRight,
+        ] {
+            assert_eq!(direction.to_string().parse::<Direction>(), Ok(direction));
+        }
+        assert_eq!("UL".parse::<Direction>(), Ok(Direction::UpLeft));
+        assert_eq!("down_right".parse::<Direction>(), Ok(Direction::DownRight));
+        assert!("north".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_recognizes_registered_gestures() {
+        let gestures = GestureRecognizer::new(MouseButton::Button2);
+        gestures.register("back", "left up").unwrap();
+        gestures.register("forward", "right, up").unwrap();
+
+        // Jitter along the way does not add segments.
+        let recognized = draw(&gestures, MouseButton::Button2, &[(500, 500), (470, 503), (420, 498), (421, 460), (418, 400)]);
+        assert_eq!(
+            recognized,
+            Some(GestureEvent {
+                name: "back".to_string(),
+                directions: vec![Direction::Left, Direction::Up],
+                button: MouseButton::Button2,
+                start: (500, 500),
+                end: (418, 400),
+            })
+        );
+
+        let recognized = draw(&gestures, MouseButton::Button2, &[(100, 100), (200, 100), (200, 0)]);
+        assert_eq!(recognized.map(|gesture| gesture.name), Some("forward".to_string()));
+
+        // Unregistered shapes, other buttons and tiny movements are ignored.
+        assert_eq!(draw(&gestures, MouseButton::Button2, &[(100, 100), (100, 200)]), None);
+        assert_eq!(draw(&gestures, MouseButton::Button1, &[(500, 500), (400, 500), (400, 400)]), None);
+        assert_eq!(draw(&gestures, MouseButton::Button2, &[(500, 500), (490, 500), (490, 490)]), None);
+    }
+
+    #[test]
+    fn test_min_segment_length() {
+        let gestures = GestureRecognizer::new(MouseButton::Button2);
+        gestures.register("down", "down").unwrap();
+        let points = [(0, 0), (0, 12)];
+
+        assert_eq!(draw(&gestures, MouseButton::Button2, &points), None);
+        gestures.set_min_segment_length(10);
+        assert!(draw(&gestures, MouseButton::Button2, &points).is_some());
+    }
+
+    #[test]
+    fn test_eight_way_gestures() {
+        let gestures = GestureRecognizer::new(MouseButton::Button3);
+        assert_eq!(
+            gestures.register("zig", "down-right up-right"),
+            Err(UiohookError::InvalidArgument("diagonal gestures need eight-way quantization"))
+        );
+
+        gestures.set_quantization(Quantization::Eight);
+        gestures.register("zig", "down-right up-right").unwrap();
+        let recognized = draw(&gestures, MouseButton::Button3, &[(0, 0), (50, 50), (100, 0)]);
+        assert_eq!(recognized.map(|gesture| gesture.name), Some("zig".to_string()));
+    }
+
+    #[test]
+    fn test_registration() {
+        let gestures = GestureRecognizer::new(MouseButton::Button2);
+        assert_eq!(gestures.register("none", " "), Err(UiohookError::InvalidArgument("gesture has no directions")));
+        assert_eq!(gestures.register("line", "up up"), Err(UiohookError::InvalidArgument("gesture repeats a direction")));
+
+        gestures.register("close", "down right").unwrap();
+        gestures.register("close", "down left").unwrap();
+        assert_eq!(draw(&gestures, MouseButton::Button2, &[(100, 0), (100, 100), (200, 100)]), None);
+        assert!(draw(&gestures, MouseButton::Button2, &[(100, 0), (100, 100), (0, 100)]).is_some());
+
+        assert!(gestures.unregister("close"));
+        assert!(!gestures.unregister("close"));
+        assert_eq!(draw(&gestures, MouseButton::Button2, &[(100, 0), (100, 100), (0, 100)]), None);
+    }
+
+    #[test]
+    fn test_injected_events() {
+        let backend = MockBackend::new();
+        let gestures = GestureRecognizer::new(MouseButton::Button2);
+        backend.dispatcher().subscribe(gestures.clone());
+        gestures.register("back", "left up").unwrap();
+        let recognized = Arc::new(Mutex::new(Vec::new()));
+        let recorded = recognized.clone();
+        gestures.on_gesture(move |gesture| recorded.lock().unwrap().push(gesture.name.clone()));
+
+        for (event_type, button, x, y) in [
+            (MouseEventType::Pressed, MouseButton::Button2, 300, 300),
+            (MouseEventType::Dragged, MouseButton::NoButton, 250, 300),
+            (MouseEventType::Dragged, MouseButton::NoButton, 250, 250),
+            (MouseEventType::Released, MouseButton::Button2, 250, 250),
+        ] {
+            backend.inject(&UiohookEvent::Mouse(mouse_event(event_type, button, x, y)));
+        }
+        assert_eq!(*recognized.lock().unwrap(), vec!["back".to_string()]);
+    }
+}
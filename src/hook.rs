@@ -6,30 +6,69 @@
 use self::keyboard::KeyboardEvent;
 use self::mouse::MouseEvent;
 use self::wheel::WheelEvent;
+use self::backend::{HookBackend, LibUiohookBackend};
+use self::channel::{ChannelGate, EventReceiver, OverflowPolicy};
+use self::dispatch::{HookStatus, Subscriber, SubscriptionId};
+use self::modifiers::Modifiers;
 use crate::error::UiohookError;
+use crate::utils::{ScreenData, SystemProperties};
 use crate::{bindings, KeyboardEventType, MouseEventType};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, OnceLock, RwLock};
-use std::thread;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+pub mod backend;
+pub mod channel;
+pub mod dispatch;
 pub mod keyboard;
+pub mod modifiers;
 pub mod mouse;
+#[cfg(feature = "async")]
+pub mod stream;
 pub mod wheel;
 
-// Only initialize the event handler once globally (global hooks are only allowed to be set once)
-static GLOBAL_HANDLER: OnceLock<Arc<RwLock<dyn EventHandler>>> = OnceLock::new();
-
 /// Trait for handling uiohook events.
 pub trait EventHandler: Send + Sync {
     /// Handle a uiohook event.
     fn handle_event(&self, event: &UiohookEvent);
 }
 
+/// Decides whether an event continues to other applications after being handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EventVerdict {
+    /// Let the event continue to other applications.
+    #[default]
+    Propagate,
+    /// Swallow the event so other applications never see it.
+    ///
+    /// Consuming events is supported on Windows and macOS. The X11 backend
+    /// observes events through XRecord and cannot suppress them.
+    Consume,
+}
+
+/// Trait for handling uiohook events with the ability to consume them.
+///
+/// This is the variant of [`EventHandler`] used to build remappers and hotkeys
+/// that swallow the original input. If any subscribed filter returns
+/// [`EventVerdict::Consume`], the event is consumed; every subscriber still
+/// observes it.
+pub trait EventFilter: Send + Sync {
+    /// Handle a uiohook event and decide whether it should propagate.
+    fn filter_event(&self, event: &UiohookEvent) -> EventVerdict;
+}
+
 /// Main struct for interacting with uiohook.
+///
+/// Each running instance subscribes its handler to its backend's
+/// [`Dispatcher`](dispatch::Dispatcher); the underlying hook thread is shared and kept alive until the
+/// last running instance on that backend is stopped or dropped.
 pub struct Uiohook {
-    event_handler: Arc<RwLock<dyn EventHandler>>,
-    running: Arc<AtomicBool>,
-    thread_handle: RwLock<Option<thread::JoinHandle<()>>>,
+    backend: Arc<dyn HookBackend>,
+    subscriber: Subscriber,
+    status: Arc<HookStatus>,
+    subscription: Mutex<Option<SubscriptionId>>,
+    // Set for instances created by `with_channel` or `with_stream`.
+    channel_gate: Option<ChannelGate>,
 }
 
 impl Uiohook {
@@ -56,19 +95,188 @@ impl Uiohook {
     /// ```
     pub fn new<H: EventHandler + 'static>(event_handler: H) -> Self {
         Self {
-            event_handler: Arc::new(RwLock::new(event_handler)),
-            running: Arc::new(AtomicBool::new(false)),
-            thread_handle: RwLock::new(None),
+            backend: Arc::new(LibUiohookBackend),
+            subscriber: Subscriber::Handler(Arc::new(event_handler)),
+            status: Arc::new(HookStatus::default()),
+            subscription: Mutex::new(None),
+            channel_gate: None,
+        }
+    }
+
+    /// Create a new Uiohook instance with an event filter that may consume events.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_filter` - An implementation of the `EventFilter` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uiohook_rs::{EventFilter, EventVerdict, Uiohook, UiohookEvent};
+    /// use uiohook_rs::hook::keyboard::KeyCode;
+    ///
+    /// struct SwallowCapsLock;
+    ///
+    /// impl EventFilter for SwallowCapsLock {
+    ///     fn filter_event(&self, event: &UiohookEvent) -> EventVerdict {
+    ///         match event {
+    ///             UiohookEvent::Keyboard(ke) if ke.key_code == KeyCode::CapsLock => EventVerdict::Consume,
+    ///             _ => EventVerdict::Propagate,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let hook = Uiohook::with_filter(SwallowCapsLock);
+    /// ```
+    pub fn with_filter<F: EventFilter + 'static>(event_filter: F) -> Self {
+        Self {
+            backend: Arc::new(LibUiohookBackend),
+            subscriber: Subscriber::Filter(Arc::new(event_filter)),
+            status: Arc::new(HookStatus::default()),
+            subscription: Mutex::new(None),
+            channel_gate: None,
+        }
+    }
+
+    /// Create a new Uiohook instance that delivers events into a bounded channel.
+    ///
+    /// Events are pulled from the returned [`EventReceiver`] on any thread instead
+    /// of being handled on the hook thread. When the channel holds `capacity`
+    /// events, `policy` decides which events are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use uiohook_rs::Uiohook;
+    /// use uiohook_rs::hook::channel::OverflowPolicy;
+    ///
+    /// let (hook, events) = Uiohook::with_channel(256, OverflowPolicy::CoalesceMouseMoves);
+    /// hook.run().expect("Failed to run uiohook");
+    ///
+    /// std::thread::spawn(move || {
+    ///     for event in events.iter() {
+    ///         println!("Event: {:?}", event);
+    ///     }
+    /// });
+    /// ```
+    pub fn with_channel(capacity: usize, policy: OverflowPolicy) -> (Self, EventReceiver) {
+        let (handler, receiver) = channel::channel(capacity, policy);
+        let gate = handler.gate();
+        let mut hook = Self::new(handler);
+        hook.channel_gate = Some(gate);
+        (hook, receiver)
+    }
+
+    /// Create a new Uiohook instance that delivers events as an async [`Stream`].
+    ///
+    /// The stream is backed by the same bounded buffer as
+    /// [`Uiohook::with_channel`]; `policy` decides what happens when the
+    /// consumer falls `capacity` events behind.
+    ///
+    /// [`Stream`]: futures_core::Stream
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use uiohook_rs::Uiohook;
+    /// use uiohook_rs::hook::channel::OverflowPolicy;
+    ///
+    /// # async fn example() {
+    /// let (hook, mut events) = Uiohook::with_stream(256, OverflowPolicy::DropOldest);
+    /// hook.run().expect("Failed to run uiohook");
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     println!("Event: {:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn with_stream(capacity: usize, policy: OverflowPolicy) -> (Self, stream::EventStream) {
+        let (hook, receiver) = Self::with_channel(capacity, policy);
+        (hook, stream::EventStream::new(receiver))
+    }
+
+    /// Replace the backend that captures and posts events.
+    ///
+    /// Instances use [`LibUiohookBackend`] unless told otherwise. A running
+    /// instance is stopped before the backend is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend to use, such as a [`MockBackend`](backend::MockBackend) in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+    /// use uiohook_rs::hook::backend::MockBackend;
+    ///
+    /// struct MyHandler;
+    ///
+    /// impl EventHandler for MyHandler {
+    ///     fn handle_event(&self, event: &UiohookEvent) {
+    ///         println!("Event: {:?}", event);
+    ///     }
+    /// }
+    ///
+    /// let backend = MockBackend::new();
+    /// let hook = Uiohook::new(MyHandler).with_backend(backend.clone());
+    /// ```
+    pub fn with_backend<B: HookBackend>(mut self, backend: B) -> Self {
+        if self.is_running() {
+            let _ = self.stop();
         }
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Returns the backend this instance captures and posts events through.
+    pub fn backend(&self) -> &dyn HookBackend {
+        &*self.backend
+    }
+
+    /// Returns the keyboard and pointer settings reported by the backend.
+    ///
+    /// Settings the system does not report are `None`.
+    pub fn system_properties(&self) -> SystemProperties {
+        self.backend.system_properties()
+    }
+
+    /// Returns the multi-click time in milliseconds reported by the backend, or
+    /// `None` if the system does not report it.
+    pub fn multi_click_time(&self) -> Option<i64> {
+        self.backend.multi_click_time()
+    }
+
+    /// Returns the screens reported by the backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the screen information cannot be read.
+    pub fn screen_info(&self) -> Result<Vec<ScreenData>, UiohookError> {
+        self.backend.screen_info()
     }
 
     /// Run the uiohook event loop.
     ///
-    /// This method will block until `stop()` is called or an error occurs.
+    /// The hook runs on a background thread. This method blocks until the hook
+    /// has started, then returns; the hook keeps running until `stop()` is
+    /// called or an error occurs. Errors after startup are available from
+    /// [`Uiohook::last_error`] and [`Uiohook::set_error_handler`].
     ///
     /// # Errors
     ///
-    /// Returns a `UiohookError` if the hook fails to start or encounters an error while running.
+    /// Returns `UiohookError::AlreadyRunning` if this instance is already
+    /// running, or the error reported by the backend if the hook fails to start.
     ///
     /// # Examples
     ///
@@ -92,31 +300,73 @@ impl Uiohook {
     /// Note: In a real application, you may need to run a platform-specific event loop (e.g., CFRunLoop on macOS)
     /// or spawn the hook in a separate thread.
     pub fn run(&self) -> Result<(), UiohookError> {
-        if self.running.swap(true, Ordering::SeqCst) {
+        if self.status.running.swap(true, Ordering::SeqCst) {
             return Err(UiohookError::AlreadyRunning);
         }
 
-        // Use `get_or_init` to ensure the once initialization
-        GLOBAL_HANDLER.get_or_init(|| {
-            unsafe {
-                bindings::hook_set_dispatch_proc(Some(dispatch_proc_wrapper));
+        self.status.clear_error();
+        self.set_channel_blocking(true);
+        let mut subscription = self.lock_subscription();
+        match self.backend.dispatcher().acquire(
+            Arc::clone(&self.backend),
+            self.subscriber.clone(),
+            Arc::clone(&self.status),
+        ) {
+            Ok(id) => {
+                *subscription = Some(id);
+                Ok(())
             }
-            Arc::clone(&self.event_handler)
-        });
-
-        let running = self.running.clone();
-        let thread = thread::spawn(move || {
-            while running.load(Ordering::SeqCst) {
-                let result = unsafe { bindings::hook_run() };
-                if result != bindings::UIOHOOK_SUCCESS as i32 {
-                    eprintln!("Error in hook_run: {:?}", UiohookError::from(result as u32));
-                    break;
-                }
+            Err(e) => {
+                self.status.running.store(false, Ordering::SeqCst);
+                Err(e)
             }
-        });
+        }
+    }
+
+    /// Returns `true` while this instance is running.
+    ///
+    /// Becomes `false` after `stop()` or when the hook thread dies.
+    pub fn is_running(&self) -> bool {
+        self.status.running.load(Ordering::SeqCst)
+    }
 
-        *self.thread_handle.write().unwrap() = Some(thread);
-        Ok(())
+    /// Returns the error that stopped the hook after it had started, if any.
+    ///
+    /// Cleared by the next call to `run()`.
+    pub fn last_error(&self) -> Option<UiohookError> {
+        self.status.last_error()
+    }
+
+    /// Set a callback invoked when an error stops the hook after it had started.
+    ///
+    /// The callback runs on the hook thread and must not call `run()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The function to call with the error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+    ///
+    /// struct MyHandler;
+    ///
+    /// impl EventHandler for MyHandler {
+    ///     fn handle_event(&self, event: &UiohookEvent) {
+    ///         println!("Event: {:?}", event);
+    ///     }
+    /// }
+    ///
+    /// let hook = Uiohook::new(MyHandler);
+    /// hook.set_error_handler(|e| eprintln!("Hook stopped: {}", e));
+    /// hook.run().expect("Failed to run uiohook");
+    /// ```
+    pub fn set_error_handler<F>(&self, handler: F)
+    where
+        F: Fn(UiohookError) + Send + Sync + 'static,
+    {
+        self.status.set_error_callback(Some(Arc::new(handler)));
     }
 
     /// Stop the uiohook event loop.
@@ -147,21 +397,38 @@ impl Uiohook {
     /// hook.stop().expect("Failed to stop uiohook");
     /// ```
     pub fn stop(&self) -> Result<(), UiohookError> {
-        if !self.running.swap(false, Ordering::SeqCst) {
+        if !self.status.running.swap(false, Ordering::SeqCst) {
             return Err(UiohookError::NotRunning);
         }
 
-        let result = unsafe { bindings::hook_stop() };
-
-        if let Some(thread) = self.thread_handle.write().unwrap().take() {
-            thread.join().map_err(|_| UiohookError::Failure)?;
+        self.set_channel_blocking(false);
+        match self.lock_subscription().take() {
+            Some(id) => self.backend.dispatcher().release(&*self.backend, id),
+            None => Ok(()),
         }
+    }
 
-        if result == bindings::UIOHOOK_SUCCESS as i32 {
-            Ok(())
-        } else {
-            Err(UiohookError::from(result as u32))
+    /// Stop the uiohook event loop without blocking the async executor.
+    ///
+    /// Behaves like [`Uiohook::stop`], but waits for the hook thread to exit on
+    /// a helper thread instead of joining it on the caller's thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the hook fails to stop.
+    #[cfg(feature = "async")]
+    pub async fn stop_async(&self) -> Result<(), UiohookError> {
+        if !self.status.running.swap(false, Ordering::SeqCst) {
+            return Err(UiohookError::NotRunning);
         }
+
+        self.set_channel_blocking(false);
+        let Some(id) = self.lock_subscription().take() else {
+            return Ok(());
+        };
+        let (result, thread) = self.backend.dispatcher().detach(&*self.backend, id);
+        stream::ThreadExit::new(thread).await?;
+        result
     }
 
     /// Post a synthetic event.
@@ -177,7 +444,7 @@ impl Uiohook {
     /// # Examples
     ///
     /// ```no_run
-    /// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+    /// use uiohook_rs::{EventMeta, Uiohook, EventHandler, UiohookEvent};
     /// use uiohook_rs::hook::keyboard::{KeyboardEvent, KeyboardEventType, key_tap, KeyCode};
     ///
     /// struct MyHandler;
@@ -197,14 +464,60 @@ impl Uiohook {
     ///     key_code: KeyCode::A,
     ///     raw_code: 0x41,
     ///     key_char: Some('A'),
+    ///     meta: EventMeta::default(),
     /// }));
     /// ```
     pub fn post_event(&self, event: &UiohookEvent) -> Result<(), UiohookError> {
-        let mut raw_event = event.to_raw_event();
-        unsafe {
-            bindings::hook_post_event(&mut raw_event);
+        self.backend.post_event(event)
+    }
+
+    // The hook thread may be blocked on a full channel whose consumer is
+    // waiting for `stop` to return, so stopping releases it.
+    fn set_channel_blocking(&self, blocking: bool) {
+        if let Some(gate) = &self.channel_gate {
+            gate.set_blocking(blocking);
+        }
+    }
+
+    // A panicking handler must not make `stop` or `Drop` panic too.
+    fn lock_subscription(&self) -> MutexGuard<'_, Option<SubscriptionId>> {
+        self.subscription.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for Uiohook {
+    fn drop(&mut self) {
+        if self.is_running() {
+            let _ = self.stop();
+        }
+    }
+}
+
+/// Timestamp and modifier state attached to every input event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EventMeta {
+    /// When the event occurred, as milliseconds since the Unix epoch reported by libuiohook.
+    pub time: Duration,
+    /// The modifier keys, mouse buttons and lock states active when the event occurred.
+    pub modifiers: Modifiers,
+}
+
+impl EventMeta {
+    /// Create event metadata from a timestamp and modifier state.
+    pub fn new(time: Duration, modifiers: Modifiers) -> Self {
+        EventMeta { time, modifiers }
+    }
+
+    /// Returns the event time as a `SystemTime`.
+    pub fn system_time(&self) -> SystemTime {
+        UNIX_EPOCH + self.time
+    }
+
+    fn from_raw(event: &bindings::uiohook_event) -> Self {
+        EventMeta {
+            time: Duration::from_millis(event.time),
+            modifiers: Modifiers::from(event.mask),
         }
-        Ok(())
     }
 }
 
@@ -224,7 +537,19 @@ pub enum UiohookEvent {
 }
 
 impl UiohookEvent {
-    fn from_raw_event(event: &bindings::uiohook_event) -> Self {
+    /// Returns the timestamp and modifier state of an input event.
+    ///
+    /// `HookEnabled` and `HookDisabled` carry no metadata.
+    pub fn meta(&self) -> Option<&EventMeta> {
+        match self {
+            UiohookEvent::Keyboard(ke) => Some(&ke.meta),
+            UiohookEvent::Mouse(me) => Some(&me.meta),
+            UiohookEvent::Wheel(we) => Some(&we.meta),
+            UiohookEvent::HookEnabled | UiohookEvent::HookDisabled => None,
+        }
+    }
+
+    pub(crate) fn from_raw_event(event: &bindings::uiohook_event) -> Self {
         use bindings::event_type::*;
         match event.type_ {
             EVENT_HOOK_ENABLED => UiohookEvent::HookEnabled,
@@ -249,6 +574,7 @@ impl UiohookEvent {
             EVENT_KEY_TYPED => KeyboardEventType::Typed,
             _ => unreachable!(),
         };
+        ke.meta = EventMeta::from_raw(event);
         ke
     }
 
@@ -263,11 +589,14 @@ impl UiohookEvent {
             EVENT_MOUSE_DRAGGED => MouseEventType::Dragged,
             _ => unreachable!(),
         };
+        me.meta = EventMeta::from_raw(event);
         me
     }
 
     fn create_wheel_event(event: &bindings::uiohook_event) -> WheelEvent {
-        WheelEvent::from(unsafe { &event.data.wheel })
+        let mut we = WheelEvent::from(unsafe { &event.data.wheel });
+        we.meta = EventMeta::from_raw(event);
+        we
     }
 
     fn to_raw_event(&self) -> bindings::uiohook_event {
@@ -279,7 +608,12 @@ impl UiohookEvent {
             reserved: 0,
             data: unsafe { std::mem::zeroed() },
         };
-        
+
+        if let Some(meta) = self.meta() {
+            raw_event.time = meta.time.as_millis() as u64;
+            raw_event.mask = meta.modifiers.into();
+        }
+
         match self {
             UiohookEvent::HookEnabled => {
                 raw_event.type_ = EVENT_HOOK_ENABLED;
@@ -293,9 +627,7 @@ impl UiohookEvent {
                     KeyboardEventType::Released => EVENT_KEY_RELEASED,
                     KeyboardEventType::Typed => EVENT_KEY_TYPED,
                 };
-                raw_event.data.keyboard.keycode = ke.key_code as u16;
-                raw_event.data.keyboard.rawcode = ke.raw_code;
-                raw_event.data.keyboard.keychar = ke.key_char.map(|c| c as u16).unwrap_or(0);
+                raw_event.data.keyboard = bindings::keyboard_event_data::from(ke);
             }
             UiohookEvent::Mouse(me) => {
                 raw_event.type_ = match me.event_type {
@@ -305,25 +637,41 @@ impl UiohookEvent {
                     MouseEventType::Clicked => EVENT_MOUSE_CLICKED,
                     MouseEventType::Dragged => EVENT_MOUSE_DRAGGED,
                 };
-                raw_event.data.mouse.button = me.button as u16;
-                raw_event.data.mouse.clicks = me.clicks;
-                raw_event.data.mouse.x = me.x;
-                raw_event.data.mouse.y = me.y;
+                raw_event.data.mouse = bindings::mouse_event_data::from(me);
             }
             UiohookEvent::Wheel(we) => {
                 raw_event.type_ = EVENT_MOUSE_WHEEL;
-                raw_event.data.wheel.clicks = we.clicks;
-                raw_event.data.wheel.x = we.x;
-                raw_event.data.wheel.y = we.y;
-                raw_event.data.wheel.type_ = we.type_;
-                raw_event.data.wheel.amount = we.amount;
-                raw_event.data.wheel.rotation = we.rotation;
-                raw_event.data.wheel.direction = we.direction;
+                raw_event.data.wheel = bindings::mouse_wheel_event_data::from(we);
             }
         }
 
         raw_event
     }
+
+    // libuiohook carries a single UTF-16 unit per event, so a typed character
+    // outside the Basic Multilingual Plane becomes a surrogate pair of events.
+    fn to_raw_events(&self) -> Vec<bindings::uiohook_event> {
+        let raw_event = self.to_raw_event();
+        match self {
+            UiohookEvent::Keyboard(KeyboardEvent {
+                event_type: KeyboardEventType::Typed,
+                key_char: Some(c),
+                ..
+            }) if c.len_utf16() == 2 => {
+                let mut units = [0; 2];
+                c.encode_utf16(&mut units);
+                units
+                    .iter()
+                    .map(|&unit| {
+                        let mut half = raw_event;
+                        half.data.keyboard.keychar = unit;
+                        half
+                    })
+                    .collect()
+            }
+            _ => vec![raw_event],
+        }
+    }
 }
 
 impl From<&bindings::uiohook_event> for UiohookEvent {
@@ -332,19 +680,6 @@ impl From<&bindings::uiohook_event> for UiohookEvent {
     }
 }
 
-unsafe extern "C" fn dispatch_proc_wrapper(event: *mut bindings::uiohook_event) {
-    dispatch_proc(&*event);
-}
-
-fn dispatch_proc(event: &bindings::uiohook_event) {
-    if let Some(handler) = GLOBAL_HANDLER.get() {
-        let event = UiohookEvent::from_raw_event(event);
-        if let Ok(guard) = handler.read() {
-            guard.handle_event(&event);
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,6 +699,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_event_meta_round_trip() {
+        let mut raw_event = UiohookEvent::Mouse(MouseEvent {
+            event_type: MouseEventType::Moved,
+            button: self::mouse::MouseButton::NoButton,
+            clicks: 0,
+            x: 10,
+            y: 20,
+            meta: EventMeta::default(),
+        })
+        .to_raw_event();
+        raw_event.time = 1_700_000_000_123;
+        raw_event.mask = bindings::MASK_SHIFT_L as u16;
+
+        let event = UiohookEvent::from_raw_event(&raw_event);
+        let meta = event.meta().expect("mouse events carry metadata");
+        assert_eq!(meta.time, Duration::from_millis(1_700_000_000_123));
+        assert_eq!(meta.modifiers, Modifiers::SHIFT_L);
+        assert!(meta.modifiers.shift_left());
+        assert_eq!(meta.system_time(), UNIX_EPOCH + Duration::from_millis(1_700_000_000_123));
+
+        let posted = event.to_raw_event();
+        assert_eq!(posted.time, 1_700_000_000_123);
+        assert_eq!(posted.mask, bindings::MASK_SHIFT_L as u16);
+        assert!(UiohookEvent::HookEnabled.meta().is_none());
+    }
+
     #[test]
     fn test_uiohook_run_and_stop() {
         let event_count = Arc::new(AtomicUsize::new(0));
@@ -371,15 +733,18 @@ mod tests {
             event_count: event_count.clone(),
         };
 
-        let hook = Uiohook::new(handler);
+        let backend = backend::MockBackend::new();
+        backend.set_loopback(true);
+        let hook = Uiohook::new(handler).with_backend(backend.clone());
 
         // Run the hook
         if let Err(e) = hook.run() {
             panic!("Failed to run uiohook: {}", e);
         }
 
-        // Wait a short while for the hook to start
-        std::thread::sleep(Duration::from_millis(100));
+        // `run` returns once the hook has started
+        assert!(backend.is_running());
+        assert!(hook.is_running());
 
         // Post a test event
         let test_event = UiohookEvent::Keyboard(KeyboardEvent {
@@ -387,18 +752,182 @@ mod tests {
             key_code: self::keyboard::KeyCode::A,
             raw_code: 0x41,
             key_char: Some('A'),
+            meta: EventMeta::default(),
         });
         hook.post_event(&test_event).expect("Failed to post event");
-
-        // Wait for the event to be processed
-        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(backend.posted_events().len(), 1);
 
         // Check if the event was processed
         assert_eq!(event_count.load(Ordering::SeqCst), 1, "Event was not processed");
 
         // Stop the hook
         hook.stop().expect("Failed to stop uiohook");
+        assert!(!backend.is_running());
+        assert!(matches!(hook.stop(), Err(UiohookError::NotRunning)));
+    }
 
-        std::thread::sleep(Duration::from_millis(100));
+    #[test]
+    fn test_injected_events_reach_handler() {
+        let event_count = Arc::new(AtomicUsize::new(0));
+        let backend = backend::MockBackend::new();
+        let hook = Uiohook::new(TestHandler {
+            event_count: event_count.clone(),
+        })
+        .with_backend(backend.clone());
+
+        let key = UiohookEvent::Keyboard(KeyboardEvent {
+            event_type: KeyboardEventType::Pressed,
+            key_code: keyboard::KeyCode::A,
+            raw_code: 0x41,
+            key_char: None,
+            meta: EventMeta::default(),
+        });
+        backend.inject(&key);
+        assert_eq!(event_count.load(Ordering::SeqCst), 0, "Handler subscribed before run");
+
+        hook.run().expect("Failed to run uiohook");
+        backend.inject(&key);
+        hook.stop().expect("Failed to stop uiohook");
+        backend.inject(&key);
+
+        assert_eq!(event_count.load(Ordering::SeqCst), 1);
+        assert_eq!(backend.dispatcher().subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_posted_codes_round_trip() {
+        let backend = backend::MockBackend::new();
+        backend.set_loopback(true);
+        let (hook, events) = Uiohook::with_channel(1024, OverflowPolicy::DropNewest);
+        let hook = hook.with_backend(backend.clone());
+        hook.run().expect("Failed to run uiohook");
+
+        let mut keys: Vec<_> = (0..=u16::MAX as u32)
+            .filter_map(|raw| keyboard::KeyCode::try_from(raw).ok())
+            .collect();
+        keys.push(keyboard::KeyCode::Other(0xE0F0));
+        for &key in &keys {
+            keyboard::key_toggle(&hook, key, true).expect("Failed to post key");
+        }
+        let mut buttons: Vec<_> = (0..=u16::MAX as u32)
+            .filter_map(|raw| mouse::MouseButton::try_from(raw).ok())
+            .collect();
+        buttons.push(mouse::MouseButton::Other(8));
+        for &button in &buttons {
+            mouse::mouse_press(&hook, button, 1, 2).expect("Failed to post button");
+        }
+        hook.stop().expect("Failed to stop uiohook");
+
+        // Posted events carry the VC_* and MOUSE_BUTTON* values, not enum discriminants.
+        let posted = backend.posted_events();
+        assert_eq!(posted.len(), keys.len() + buttons.len());
+        for (event, &key) in posted.iter().zip(&keys) {
+            let raw = event.to_raw_event();
+            assert_eq!(unsafe { raw.data.keyboard.keycode } as u32, u32::from(key), "{:?}", key);
+        }
+        for (event, &button) in posted[keys.len()..].iter().zip(&buttons) {
+            let raw = event.to_raw_event();
+            assert_eq!(unsafe { raw.data.mouse.button } as u32, u32::from(button), "{:?}", button);
+        }
+
+        // And the captured copies decode back to what was posted.
+        let received: Vec<_> = events
+            .try_iter()
+            .filter(|event| event.meta().is_some())
+            .collect();
+        assert_eq!(received.len(), posted.len());
+        for (received, posted) in received.iter().zip(&posted) {
+            match (received, posted) {
+                (UiohookEvent::Keyboard(a), UiohookEvent::Keyboard(b)) => {
+                    assert_eq!(a.key_code, b.key_code);
+                    assert_eq!(a.key_char, b.key_char);
+                }
+                (UiohookEvent::Mouse(a), UiohookEvent::Mouse(b)) => assert_eq!(a.button, b.button),
+                _ => panic!("{:?} was received as {:?}", posted, received),
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_reports_startup_error() {
+        let backend = backend::MockBackend::new();
+        backend.fail_next_run(UiohookError::XOpenDisplay);
+        let hook = Uiohook::new(TestHandler {
+            event_count: Arc::new(AtomicUsize::new(0)),
+        })
+        .with_backend(backend.clone());
+
+        assert_eq!(hook.run(), Err(UiohookError::XOpenDisplay));
+        assert!(!hook.is_running());
+        assert_eq!(backend.dispatcher().subscriber_count(), 0);
+
+        // The failure is not sticky.
+        hook.run().expect("Failed to run uiohook");
+        hook.stop().expect("Failed to stop uiohook");
+    }
+
+    #[test]
+    fn test_runtime_error_resets_running() {
+        let backend = backend::MockBackend::new();
+        let hook = Uiohook::new(TestHandler {
+            event_count: Arc::new(AtomicUsize::new(0)),
+        })
+        .with_backend(backend.clone());
+        let (errors_tx, errors_rx) = std::sync::mpsc::channel();
+        hook.set_error_handler(move |e| errors_tx.send(e).unwrap());
+
+        hook.run().expect("Failed to run uiohook");
+        backend.fail(UiohookError::XRecordGetContext);
+
+        assert_eq!(
+            errors_rx.recv_timeout(Duration::from_secs(5)),
+            Ok(UiohookError::XRecordGetContext)
+        );
+        assert!(!hook.is_running());
+        assert_eq!(hook.last_error(), Some(UiohookError::XRecordGetContext));
+        assert_eq!(hook.stop(), Err(UiohookError::NotRunning));
+
+        hook.run().expect("Failed to restart uiohook");
+        assert_eq!(hook.last_error(), None);
+        hook.stop().expect("Failed to stop uiohook");
+    }
+
+    #[test]
+    fn test_stop_recovers_poisoned_subscription() {
+        let backend = backend::MockBackend::new();
+        let hook = Uiohook::new(TestHandler {
+            event_count: Arc::new(AtomicUsize::new(0)),
+        })
+        .with_backend(backend.clone());
+        hook.run().expect("Failed to run uiohook");
+
+        std::thread::scope(|scope| {
+            let poisoner = scope.spawn(|| {
+                let _guard = hook.subscription.lock().unwrap();
+                panic!("poison the subscription lock");
+            });
+            assert!(poisoner.join().is_err());
+        });
+        assert!(hook.subscription.is_poisoned());
+
+        hook.stop().expect("Failed to stop uiohook");
+        assert_eq!(backend.dispatcher().subscriber_count(), 0);
+        hook.run().expect("Failed to restart uiohook");
+    }
+
+    #[test]
+    fn test_stop_releases_hook_thread_blocked_on_channel() {
+        let backend = backend::MockBackend::new();
+        // Scripted events are injected on the hook thread, which blocks once
+        // `HookEnabled` fills the channel.
+        backend.script((0..3).map(|_| UiohookEvent::HookEnabled));
+        let (hook, events) = Uiohook::with_channel(1, OverflowPolicy::Block);
+        let hook = hook.with_backend(backend.clone());
+
+        hook.run().expect("Failed to run uiohook");
+        hook.stop().expect("Failed to stop uiohook");
+        assert!(!backend.is_running());
+        assert!(matches!(events.try_recv(), Ok(UiohookEvent::HookEnabled)));
+        assert!(events.dropped_count() > 0);
     }
 }
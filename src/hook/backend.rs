@@ -0,0 +1,450 @@
+//! Hook backends.
+//!
+//! A [`HookBackend`] is what actually captures and posts input events. By
+//! default `Uiohook` uses [`LibUiohookBackend`], which talks to libuiohook and
+//! needs a live display. [`MockBackend`] runs entirely in-process so handler
+//! logic can be tested headless: tests inject events as if libuiohook had
+//! captured them and inspect the events that were posted.
+
+use super::dispatch::{self, Dispatcher};
+use super::{EventVerdict, UiohookEvent};
+use crate::bindings;
+use crate::error::{Result, UiohookError};
+use crate::utils::{self, ScreenData, SystemProperties};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+/// The source of captured events and the sink for posted events.
+pub trait HookBackend: Send + Sync + 'static {
+    /// Run the hook, delivering every captured event to [`HookBackend::dispatcher`].
+    ///
+    /// Blocks until [`HookBackend::stop`] is called. Once the hook is installed
+    /// the backend must deliver `HookEnabled`; [`Uiohook::run`](crate::Uiohook::run)
+    /// waits for it before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the hook fails to start.
+    fn run(&self) -> Result<()>;
+
+    /// Stop a running hook, making [`HookBackend::run`] return.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the hook fails to stop.
+    fn stop(&self) -> Result<()>;
+
+    /// Post a synthetic event.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the event fails to post.
+    fn post_event(&self, event: &UiohookEvent) -> Result<()>;
+
+    /// Returns the dispatcher that receives this backend's events.
+    fn dispatcher(&self) -> &Dispatcher;
+
+    /// Returns the keyboard and pointer settings of the system.
+    ///
+    /// Settings the system does not report are `None`.
+    fn system_properties(&self) -> SystemProperties;
+
+    /// Returns the maximum time in milliseconds between clicks of a
+    /// multi-click, or `None` if the system does not report it.
+    ///
+    /// Unlike [`HookBackend::system_properties`], this reads no other setting.
+    fn multi_click_time(&self) -> Option<i64>;
+
+    /// Returns information about all available screens.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `UiohookError` if the screen information cannot be read.
+    fn screen_info(&self) -> Result<Vec<ScreenData>>;
+}
+
+/// The default backend, backed by libuiohook.
+///
+/// libuiohook supports a single hook per process, so every instance shares the
+/// process-wide [`Dispatcher::global`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LibUiohookBackend;
+
+impl HookBackend for LibUiohookBackend {
+    fn run(&self) -> Result<()> {
+        crate::logger::install();
+        dispatch::install_dispatch_proc();
+        let result = unsafe { bindings::hook_run() };
+        if result == bindings::UIOHOOK_SUCCESS as i32 {
+            Ok(())
+        } else {
+            Err(UiohookError::from(result as u32))
+        }
+    }
+
+    fn stop(&self) -> Result<()> {
+        let result = unsafe { bindings::hook_stop() };
+        if result == bindings::UIOHOOK_SUCCESS as i32 {
+            Ok(())
+        } else {
+            Err(UiohookError::from(result as u32))
+        }
+    }
+
+    fn post_event(&self, event: &UiohookEvent) -> Result<()> {
+        for mut raw_event in event.to_raw_events() {
+            unsafe {
+                bindings::hook_post_event(&mut raw_event);
+            }
+        }
+        Ok(())
+    }
+
+    fn dispatcher(&self) -> &Dispatcher {
+        Dispatcher::global()
+    }
+
+    fn system_properties(&self) -> SystemProperties {
+        utils::system_properties()
+    }
+
+    fn multi_click_time(&self) -> Option<i64> {
+        utils::get_multi_click_time().ok()
+    }
+
+    fn screen_info(&self) -> Result<Vec<ScreenData>> {
+        utils::create_screen_info()
+    }
+}
+
+// Settings reported by a `MockBackend` until replaced.
+const MOCK_PROPERTIES: SystemProperties = SystemProperties {
+    auto_repeat_rate: Some(25),
+    auto_repeat_delay: Some(500),
+    pointer_acceleration_multiplier: Some(2),
+    pointer_acceleration_threshold: Some(4),
+    pointer_sensitivity: Some(10),
+    multi_click_time: Some(500),
+};
+
+// Screen reported by a `MockBackend` until replaced.
+const MOCK_SCREEN: ScreenData = ScreenData {
+    number: 1,
+    x: 0,
+    y: 0,
+    width: 1920,
+    height: 1080,
+};
+
+/// An in-process backend for testing event handlers without a display.
+///
+/// Clones share the same state, so a test keeps one clone to drive the backend
+/// after handing another to [`Uiohook::with_backend`](crate::Uiohook::with_backend).
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::{EventMeta, Uiohook, UiohookEvent};
+/// use uiohook_rs::hook::backend::MockBackend;
+/// use uiohook_rs::hook::channel::OverflowPolicy;
+/// use uiohook_rs::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+///
+/// let backend = MockBackend::new();
+/// let (hook, events) = Uiohook::with_channel(16, OverflowPolicy::DropOldest);
+/// let hook = hook.with_backend(backend.clone());
+/// hook.run().expect("Failed to run uiohook");
+///
+/// backend.inject(&UiohookEvent::Mouse(MouseEvent {
+///     event_type: MouseEventType::Moved,
+///     button: MouseButton::NoButton,
+///     clicks: 0,
+///     x: 10,
+///     y: 20,
+///     meta: EventMeta::default(),
+/// }));
+///
+/// assert!(events.try_iter().any(|event| matches!(event, UiohookEvent::Mouse(_))));
+/// hook.stop().expect("Failed to stop uiohook");
+/// ```
+#[derive(Clone)]
+pub struct MockBackend {
+    inner: Arc<MockInner>,
+}
+
+struct MockInner {
+    dispatcher: Dispatcher,
+    state: Mutex<MockState>,
+    stopped: Condvar,
+}
+
+struct MockState {
+    running: bool,
+    // Returned by the next `run` instead of starting.
+    startup_error: Option<UiohookError>,
+    // Makes the current `run` return with an error.
+    runtime_error: Option<UiohookError>,
+    loopback: bool,
+    script: Vec<UiohookEvent>,
+    posted: Vec<UiohookEvent>,
+    properties: SystemProperties,
+    screens: Vec<ScreenData>,
+}
+
+impl MockBackend {
+    /// Create a mock backend with one 1920x1080 screen and typical system settings.
+    pub fn new() -> Self {
+        MockBackend {
+            inner: Arc::new(MockInner {
+                dispatcher: Dispatcher::new(),
+                state: Mutex::new(MockState {
+                    running: false,
+                    startup_error: None,
+                    runtime_error: None,
+                    loopback: false,
+                    script: Vec::new(),
+                    posted: Vec::new(),
+                    properties: MOCK_PROPERTIES,
+                    screens: vec![MOCK_SCREEN],
+                }),
+                stopped: Condvar::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.inner.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Deliver an event to subscribers as if libuiohook had captured it.
+    ///
+    /// The event goes through the same raw conversion as a real capture, so
+    /// fields that do not survive the round trip are lost here too. A typed
+    /// character outside the Basic Multilingual Plane is split into a surrogate
+    /// pair and reassembled, like one typed on a real keyboard.
+    ///
+    /// # Returns
+    ///
+    /// [`EventVerdict::Consume`] if a subscribed filter consumed the event.
+    pub fn inject(&self, event: &UiohookEvent) -> EventVerdict {
+        let mut verdict = EventVerdict::Propagate;
+        for mut raw_event in event.to_raw_events() {
+            if self.inner.dispatcher.dispatch_raw(&mut raw_event) == EventVerdict::Consume {
+                verdict = EventVerdict::Consume;
+            }
+        }
+        verdict
+    }
+
+    /// Make the next run fail to start with `error`, as if libuiohook could not
+    /// install its hook.
+    pub fn fail_next_run(&self, error: UiohookError) {
+        self.lock().startup_error = Some(error);
+    }
+
+    /// Make a running hook die with `error`, as if libuiohook failed after startup.
+    ///
+    /// Has no effect unless the hook is running.
+    pub fn fail(&self, error: UiohookError) {
+        let mut state = self.lock();
+        if state.running {
+            state.runtime_error = Some(error);
+            self.inner.stopped.notify_all();
+        }
+    }
+
+    /// Queue events to be injected right after `HookEnabled` the next time the hook runs.
+    pub fn script<I: IntoIterator<Item = UiohookEvent>>(&self, events: I) {
+        self.lock().script.extend(events);
+    }
+
+    /// Returns every event posted through this backend, oldest first.
+    pub fn posted_events(&self) -> Vec<UiohookEvent> {
+        self.lock().posted.clone()
+    }
+
+    /// Remove and return every event posted through this backend.
+    pub fn take_posted_events(&self) -> Vec<UiohookEvent> {
+        std::mem::take(&mut self.lock().posted)
+    }
+
+    /// When enabled, posted events are also injected while the hook runs,
+    /// like synthetic input being captured by a real hook.
+    pub fn set_loopback(&self, enabled: bool) {
+        self.lock().loopback = enabled;
+    }
+
+    /// Replace the settings returned by [`HookBackend::system_properties`].
+    pub fn set_system_properties(&self, properties: SystemProperties) {
+        self.lock().properties = properties;
+    }
+
+    /// Replace the screens returned by [`HookBackend::screen_info`].
+    pub fn set_screens(&self, screens: Vec<ScreenData>) {
+        self.lock().screens = screens;
+    }
+
+    /// Returns `true` while [`HookBackend::run`] is executing.
+    pub fn is_running(&self) -> bool {
+        self.lock().running
+    }
+
+    /// Returns a `Uiohook` that posts through a clone of this backend, for
+    /// tests that only inspect posted events.
+    #[cfg(test)]
+    pub(crate) fn hook(&self) -> crate::Uiohook {
+        let (hook, _events) = crate::Uiohook::with_channel(1, super::channel::OverflowPolicy::DropNewest);
+        hook.with_backend(self.clone())
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HookBackend for MockBackend {
+    fn run(&self) -> Result<()> {
+        let mut state = self.lock();
+        // The session may already have ended before this thread got here.
+        if !self.inner.dispatcher.is_hook_running() {
+            return Ok(());
+        }
+        if let Some(error) = state.startup_error.take() {
+            return Err(error);
+        }
+        state.running = true;
+        let script = std::mem::take(&mut state.script);
+        drop(state);
+
+        self.inject(&UiohookEvent::HookEnabled);
+        for event in &script {
+            self.inject(event);
+        }
+
+        let mut state = self.lock();
+        while self.inner.dispatcher.is_hook_running() && state.runtime_error.is_none() {
+            state = self
+                .inner
+                .stopped
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        state.running = false;
+        if let Some(error) = state.runtime_error.take() {
+            return Err(error);
+        }
+        drop(state);
+
+        self.inject(&UiohookEvent::HookDisabled);
+        Ok(())
+    }
+
+    fn stop(&self) -> Result<()> {
+        let _state = self.lock();
+        self.inner.stopped.notify_all();
+        Ok(())
+    }
+
+    fn post_event(&self, event: &UiohookEvent) -> Result<()> {
+        let mut state = self.lock();
+        state.posted.push(event.clone());
+        let echo = state.loopback && state.running;
+        drop(state);
+
+        if echo {
+            self.inject(event);
+        }
+        Ok(())
+    }
+
+    fn dispatcher(&self) -> &Dispatcher {
+        &self.inner.dispatcher
+    }
+
+    fn system_properties(&self) -> SystemProperties {
+        self.lock().properties
+    }
+
+    fn multi_click_time(&self) -> Option<i64> {
+        self.lock().properties.multi_click_time
+    }
+
+    fn screen_info(&self) -> Result<Vec<ScreenData>> {
+        Ok(self.lock().screens.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::EventFilter;
+
+    struct ConsumeAll;
+
+    impl EventFilter for ConsumeAll {
+        fn filter_event(&self, _event: &UiohookEvent) -> EventVerdict {
+            EventVerdict::Consume
+        }
+    }
+
+    #[test]
+    fn test_inject_reports_consumed_events() {
+        let backend = MockBackend::new();
+        assert_eq!(backend.inject(&UiohookEvent::HookEnabled), EventVerdict::Propagate);
+
+        backend.dispatcher().subscribe_filter(ConsumeAll);
+        assert_eq!(backend.inject(&UiohookEvent::HookEnabled), EventVerdict::Consume);
+    }
+
+    #[test]
+    fn test_inject_astral_character() {
+        use crate::hook::keyboard::{KeyCode, KeyboardEvent, KeyboardEventType};
+        use crate::hook::EventHandler;
+
+        struct Record(Arc<Mutex<Vec<UiohookEvent>>>);
+
+        impl EventHandler for Record {
+            fn handle_event(&self, event: &UiohookEvent) {
+                self.0.lock().unwrap().push(event.clone());
+            }
+        }
+
+        let backend = MockBackend::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        backend.dispatcher().subscribe(Record(events.clone()));
+        backend.inject(&UiohookEvent::Keyboard(KeyboardEvent {
+            event_type: KeyboardEventType::Typed,
+            key_code: KeyCode::Undefined,
+            raw_code: 0,
+            key_char: Some('🦀'),
+            meta: Default::default(),
+        }));
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], UiohookEvent::Keyboard(ke) if ke.key_char == Some('🦀')));
+    }
+
+    #[test]
+    fn test_posted_events_are_recorded() {
+        let backend = MockBackend::new();
+        backend.post_event(&UiohookEvent::HookEnabled).unwrap();
+        backend.post_event(&UiohookEvent::HookDisabled).unwrap();
+
+        assert_eq!(backend.posted_events().len(), 2);
+        assert_eq!(backend.take_posted_events().len(), 2);
+        assert!(backend.posted_events().is_empty());
+    }
+
+    #[test]
+    fn test_system_information() {
+        let backend = MockBackend::new();
+        assert_eq!(backend.system_properties().multi_click_time, Some(500));
+        assert_eq!(backend.screen_info().unwrap().len(), 1);
+
+        backend.set_system_properties(SystemProperties::default());
+        backend.set_screens(Vec::new());
+        assert_eq!(backend.system_properties(), SystemProperties::default());
+        assert!(backend.screen_info().unwrap().is_empty());
+    }
+}
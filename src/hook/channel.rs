@@ -0,0 +1,448 @@
+//! Channel-based event delivery.
+//!
+//! Instead of implementing [`EventHandler`], events can be pulled from an
+//! [`EventReceiver`] on any thread. The channel is bounded; what happens when it
+//! is full is decided by an [`OverflowPolicy`].
+
+use super::mouse::MouseEventType;
+use super::{EventHandler, UiohookEvent};
+use std::collections::VecDeque;
+use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::task::Waker;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Determines what happens when an event arrives while the channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued event to make room for the new one.
+    DropOldest,
+    /// Discard the new event.
+    DropNewest,
+    /// Replace the most recently queued mouse move with a new mouse move, so a
+    /// slow consumer only sees the latest cursor position. Other events fall
+    /// back to [`OverflowPolicy::DropOldest`].
+    CoalesceMouseMoves,
+    /// Block the hook thread until the consumer makes room.
+    ///
+    /// While blocked no other events are processed, which on some platforms
+    /// stalls system-wide input. Only use this with a consumer that keeps up.
+    /// Once the owning `Uiohook` starts stopping, events that do not fit are
+    /// dropped instead, so stopping never waits on the consumer.
+    Block,
+}
+
+pub(crate) struct Shared {
+    state: Mutex<State>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+struct State {
+    queue: VecDeque<UiohookEvent>,
+    sender_alive: bool,
+    receiver_alive: bool,
+    // Cleared while the owning `Uiohook` stops, so `Block` cannot stall the hook thread.
+    blocking: bool,
+    dropped: u64,
+    // Set by an async consumer waiting for the next event.
+    waker: Option<Waker>,
+}
+
+impl Shared {
+    fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(capacity > 0, "channel capacity must be non-zero");
+        Self {
+            state: Mutex::new(State {
+                queue: VecDeque::with_capacity(capacity),
+                sender_alive: true,
+                receiver_alive: true,
+                blocking: true,
+                dropped: 0,
+                waker: None,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, event: UiohookEvent) {
+        let mut state = self.lock();
+        if !state.receiver_alive {
+            return;
+        }
+
+        if state.queue.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    state.dropped += 1;
+                }
+                OverflowPolicy::DropNewest => {
+                    state.dropped += 1;
+                    return;
+                }
+                OverflowPolicy::CoalesceMouseMoves => {
+                    if is_mouse_move(&event) && state.queue.back().is_some_and(is_mouse_move) {
+                        state.queue.pop_back();
+                    } else {
+                        state.queue.pop_front();
+                    }
+                    state.dropped += 1;
+                }
+                OverflowPolicy::Block => {
+                    while state.queue.len() >= self.capacity && state.receiver_alive && state.blocking {
+                        state = self.not_full.wait(state).unwrap_or_else(PoisonError::into_inner);
+                    }
+                    if !state.receiver_alive {
+                        return;
+                    }
+                    if state.queue.len() >= self.capacity {
+                        state.dropped += 1;
+                        return;
+                    }
+                }
+            }
+        }
+
+        state.queue.push_back(event);
+        let waker = state.waker.take();
+        drop(state);
+        self.not_empty.notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn pop(&self, state: &mut State) -> Option<UiohookEvent> {
+        let event = state.queue.pop_front();
+        if event.is_some() {
+            self.not_full.notify_one();
+        }
+        event
+    }
+}
+
+fn is_mouse_move(event: &UiohookEvent) -> bool {
+    matches!(
+        event,
+        UiohookEvent::Mouse(me)
+            if matches!(me.event_type, MouseEventType::Moved | MouseEventType::Dragged)
+    )
+}
+
+/// Create a bounded channel: the handler half feeds it, the receiver half drains it.
+pub(crate) fn channel(capacity: usize, policy: OverflowPolicy) -> (ChannelHandler, EventReceiver) {
+    let shared = Arc::new(Shared::new(capacity, policy));
+    (
+        ChannelHandler {
+            shared: Arc::clone(&shared),
+        },
+        EventReceiver { shared },
+    )
+}
+
+/// Event handler that forwards every event into a channel.
+pub(crate) struct ChannelHandler {
+    shared: Arc<Shared>,
+}
+
+impl ChannelHandler {
+    /// Returns a gate controlling whether this channel may block its sender.
+    pub(crate) fn gate(&self) -> ChannelGate {
+        ChannelGate {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+/// Lets the owning `Uiohook` keep [`OverflowPolicy::Block`] from stalling a
+/// stopping hook thread, which would otherwise wait on a consumer that may be
+/// waiting on the stop.
+#[derive(Clone)]
+pub(crate) struct ChannelGate {
+    shared: Arc<Shared>,
+}
+
+impl ChannelGate {
+    /// Allow or forbid blocking. Forbidding it wakes a blocked sender, which
+    /// then drops its event.
+    pub(crate) fn set_blocking(&self, blocking: bool) {
+        self.shared.lock().blocking = blocking;
+        if !blocking {
+            self.shared.not_full.notify_all();
+        }
+    }
+}
+
+impl EventHandler for ChannelHandler {
+    fn handle_event(&self, event: &UiohookEvent) {
+        self.shared.push(event.clone());
+    }
+}
+
+impl Drop for ChannelHandler {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.sender_alive = false;
+        let waker = state.waker.take();
+        drop(state);
+        self.shared.not_empty.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The receiving half of an event channel created by
+/// [`Uiohook::with_channel`](crate::Uiohook::with_channel).
+///
+/// The receiver is disconnected once the owning `Uiohook` has been dropped and
+/// all queued events have been received.
+pub struct EventReceiver {
+    shared: Arc<Shared>,
+}
+
+impl EventReceiver {
+    /// Block until an event is available.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RecvError` once the channel is empty and disconnected.
+    pub fn recv(&self) -> Result<UiohookEvent, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(event) = self.shared.pop(&mut state) {
+                return Ok(event);
+            }
+            if !state.sender_alive {
+                return Err(RecvError);
+            }
+            state = self
+                .shared
+                .not_empty
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Return the next event if one is queued, without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TryRecvError::Empty` if no event is queued, or
+    /// `TryRecvError::Disconnected` once the channel is empty and disconnected.
+    pub fn try_recv(&self) -> Result<UiohookEvent, TryRecvError> {
+        let mut state = self.shared.lock();
+        match self.shared.pop(&mut state) {
+            Some(event) => Ok(event),
+            None if state.sender_alive => Err(TryRecvError::Empty),
+            None => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Block for at most `timeout` waiting for an event.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RecvTimeoutError::Timeout` if no event arrived in time, or
+    /// `RecvTimeoutError::Disconnected` once the channel is empty and disconnected.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<UiohookEvent, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(event) = self.shared.pop(&mut state) {
+                return Ok(event);
+            }
+            if !state.sender_alive {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = self
+                .shared
+                .not_empty
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Poll for the next event, registering `cx`'s waker if none is queued.
+    ///
+    /// Resolves to `None` once the channel is empty and disconnected.
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<UiohookEvent>> {
+        let mut state = self.shared.lock();
+        if let Some(event) = self.shared.pop(&mut state) {
+            return Poll::Ready(Some(event));
+        }
+        if !state.sender_alive {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Returns an iterator that blocks for each event until the channel is disconnected.
+    pub fn iter(&self) -> impl Iterator<Item = UiohookEvent> + '_ {
+        std::iter::from_fn(move || self.recv().ok())
+    }
+
+    /// Returns an iterator over the currently queued events, without blocking.
+    pub fn try_iter(&self) -> impl Iterator<Item = UiohookEvent> + '_ {
+        std::iter::from_fn(move || self.try_recv().ok())
+    }
+
+    /// Returns the number of queued events.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns `true` if no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of events discarded because the channel was full.
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.lock().dropped
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver_alive = false;
+        state.queue.clear();
+        drop(state);
+        self.shared.not_full.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::mouse::{MouseButton, MouseEvent};
+    use std::thread;
+
+    fn mouse(event_type: MouseEventType, x: i16) -> UiohookEvent {
+        UiohookEvent::Mouse(MouseEvent {
+            event_type,
+            button: MouseButton::NoButton,
+            clicks: 0,
+            x,
+            y: 0,
+            meta: Default::default(),
+        })
+    }
+
+    fn x_of(event: UiohookEvent) -> i16 {
+        match event {
+            UiohookEvent::Mouse(me) => me.x,
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_drop_oldest() {
+        let (handler, receiver) = channel(2, OverflowPolicy::DropOldest);
+        for x in 0..3 {
+            handler.handle_event(&mouse(MouseEventType::Pressed, x));
+        }
+
+        assert_eq!(receiver.dropped_count(), 1);
+        assert_eq!(receiver.try_iter().map(x_of).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_drop_newest() {
+        let (handler, receiver) = channel(2, OverflowPolicy::DropNewest);
+        for x in 0..3 {
+            handler.handle_event(&mouse(MouseEventType::Pressed, x));
+        }
+
+        assert_eq!(receiver.dropped_count(), 1);
+        assert_eq!(receiver.try_iter().map(x_of).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_coalesce_mouse_moves() {
+        let (handler, receiver) = channel(2, OverflowPolicy::CoalesceMouseMoves);
+        handler.handle_event(&mouse(MouseEventType::Pressed, 0));
+        for x in 1..5 {
+            handler.handle_event(&mouse(MouseEventType::Moved, x));
+        }
+
+        assert_eq!(receiver.try_iter().map(x_of).collect::<Vec<_>>(), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_block_waits_for_consumer() {
+        let (handler, receiver) = channel(1, OverflowPolicy::Block);
+        let producer = thread::spawn(move || {
+            for x in 0..3 {
+                handler.handle_event(&mouse(MouseEventType::Pressed, x));
+            }
+        });
+
+        let received: Vec<i16> = receiver.iter().map(x_of).collect();
+        producer.join().unwrap();
+        assert_eq!(received, vec![0, 1, 2]);
+        assert_eq!(receiver.dropped_count(), 0);
+    }
+
+    #[test]
+    fn test_gate_releases_blocked_sender() {
+        let (handler, receiver) = channel(1, OverflowPolicy::Block);
+        let gate = handler.gate();
+        handler.handle_event(&mouse(MouseEventType::Pressed, 0));
+        let producer = thread::spawn(move || {
+            handler.handle_event(&mouse(MouseEventType::Pressed, 1));
+            handler
+        });
+
+        thread::sleep(Duration::from_millis(10));
+        gate.set_blocking(false);
+        let handler = producer.join().unwrap();
+        assert_eq!(receiver.dropped_count(), 1);
+
+        // Full channels drop instead of blocking until blocking is allowed again.
+        handler.handle_event(&mouse(MouseEventType::Pressed, 2));
+        assert_eq!(receiver.dropped_count(), 2);
+        gate.set_blocking(true);
+        let producer = thread::spawn(move || handler.handle_event(&mouse(MouseEventType::Pressed, 3)));
+        assert_eq!(receiver.recv().map(x_of), Ok(0));
+        producer.join().unwrap();
+        assert_eq!(receiver.try_iter().map(x_of).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn test_disconnect() {
+        let (handler, receiver) = channel(4, OverflowPolicy::DropOldest);
+        handler.handle_event(&UiohookEvent::HookEnabled);
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(1)).map(|_| ()), Ok(()));
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_millis(1)).map(|_| ()),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        handler.handle_event(&UiohookEvent::HookDisabled);
+        drop(handler);
+        assert!(matches!(receiver.recv(), Ok(UiohookEvent::HookDisabled)));
+        assert_eq!(receiver.recv().map(|_| ()), Err(RecvError));
+        assert_eq!(receiver.try_recv().map(|_| ()), Err(TryRecvError::Disconnected));
+    }
+}
@@ -0,0 +1,748 @@
+//! Process-wide event dispatching.
+//!
+//! libuiohook only supports a single dispatch procedure per process, so every
+//! captured event goes through one `Dispatcher` that fans it out to all
+//! subscribed handlers. `Uiohook` instances subscribe their handler while they
+//! are running, and other code can attach independent listeners with
+//! [`subscribe`] and detach them again with [`unsubscribe`].
+
+use super::backend::HookBackend;
+use super::keyboard::{KeyboardEvent, KeyboardEventType};
+use super::{EventFilter, EventHandler, EventVerdict, UiohookEvent};
+use crate::bindings;
+use crate::error::UiohookError;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Once, PoisonError, RwLock};
+use std::thread;
+
+// The dispatcher fed by libuiohook's global dispatch procedure.
+static GLOBAL_DISPATCHER: Dispatcher = Dispatcher::new();
+
+// Installs `dispatch_proc_wrapper` as libuiohook's dispatch procedure.
+static INSTALL_DISPATCH_PROC: Once = Once::new();
+
+// Value of `uiohook_event.reserved` that tells libuiohook to consume an event.
+const EVENT_CONSUMED: u16 = 0x01;
+
+/// Identifies a handler subscribed to a [`Dispatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Callback invoked with an error that stopped a running hook.
+pub(crate) type ErrorCallback = Arc<dyn Fn(UiohookError) + Send + Sync>;
+
+/// State shared between a `Uiohook` instance and the hook thread serving it.
+#[derive(Default)]
+pub(crate) struct HookStatus {
+    pub(crate) running: AtomicBool,
+    last_error: Mutex<Option<UiohookError>>,
+    on_error: Mutex<Option<ErrorCallback>>,
+}
+
+impl HookStatus {
+    pub(crate) fn last_error(&self) -> Option<UiohookError> {
+        *lock(&self.last_error)
+    }
+
+    pub(crate) fn clear_error(&self) {
+        *lock(&self.last_error) = None;
+    }
+
+    pub(crate) fn set_error_callback(&self, callback: Option<ErrorCallback>) {
+        *lock(&self.on_error) = callback;
+    }
+
+    // Record an error that stopped the hook after it had started.
+    fn fail(&self, error: UiohookError) {
+        self.running.store(false, Ordering::SeqCst);
+        *lock(&self.last_error) = Some(error);
+        let callback = lock(&self.on_error).clone();
+        if let Some(callback) = callback {
+            if panic::catch_unwind(AssertUnwindSafe(|| callback(error))).is_err() {
+                log::error!("error callback panicked while handling: {}", error);
+            }
+        }
+    }
+}
+
+// A panicking subscriber never leaves these locks in an inconsistent state, so
+// poisoning is ignored instead of permanently breaking event delivery.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// What happens when a subscribed handler or filter panics.
+///
+/// Panics are always caught before they reach libuiohook and logged with the
+/// `log` crate; the policy decides what happens afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PanicPolicy {
+    /// Keep delivering events to the handler.
+    #[default]
+    LogAndContinue,
+    /// Unsubscribe the handler that panicked.
+    DisableHandler,
+    /// Stop the hook, reporting `UiohookError::HandlerPanicked` to every running
+    /// `Uiohook` through [`Uiohook::last_error`](crate::Uiohook::last_error).
+    StopHook,
+}
+
+/// A handler or filter subscribed to a [`Dispatcher`].
+#[derive(Clone)]
+pub(crate) enum Subscriber {
+    Handler(Arc<dyn EventHandler>),
+    Filter(Arc<dyn EventFilter>),
+}
+
+impl Subscriber {
+    fn deliver(&self, event: &UiohookEvent) -> EventVerdict {
+        match self {
+            Subscriber::Handler(handler) => {
+                handler.handle_event(event);
+                EventVerdict::Propagate
+            }
+            Subscriber::Filter(filter) => filter.filter_event(event),
+        }
+    }
+}
+
+/// Fans out captured events to every subscribed handler.
+///
+/// Every [`HookBackend`] owns one dispatcher; the libuiohook backend uses the
+/// process-wide [`Dispatcher::global`].
+pub struct Dispatcher {
+    next_id: AtomicU64,
+    subscribers: RwLock<Vec<(SubscriptionId, Subscriber)>>,
+    hook_running: AtomicBool,
+    panic_policy: Mutex<PanicPolicy>,
+    // A typed high surrogate waiting for the low surrogate that completes it.
+    pending_surrogate: Mutex<Option<(KeyboardEvent, u16)>>,
+    session: Mutex<Session>,
+    session_changed: Condvar,
+}
+
+// The hook thread shared by every running `Uiohook` on the same backend.
+struct Session {
+    members: Vec<(SubscriptionId, Arc<HookStatus>)>,
+    thread: Option<thread::JoinHandle<()>>,
+    // The backend running the thread, used to stop it from the inside.
+    backend: Option<Arc<dyn HookBackend>>,
+    state: SessionState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionState {
+    Idle,
+    // The hook thread was spawned but has not reported `HookEnabled` yet.
+    Starting,
+    Running,
+    Failed(UiohookError),
+}
+
+impl Dispatcher {
+    pub(crate) const fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            subscribers: RwLock::new(Vec::new()),
+            hook_running: AtomicBool::new(false),
+            panic_policy: Mutex::new(PanicPolicy::LogAndContinue),
+            pending_surrogate: Mutex::new(None),
+            session: Mutex::new(Session {
+                members: Vec::new(),
+                thread: None,
+                backend: None,
+                state: SessionState::Idle,
+            }),
+            session_changed: Condvar::new(),
+        }
+    }
+
+    /// Returns the process-wide dispatcher fed by libuiohook.
+    pub fn global() -> &'static Dispatcher {
+        &GLOBAL_DISPATCHER
+    }
+
+    /// Subscribe a handler to all events delivered through this dispatcher.
+    ///
+    /// The handler stays subscribed until [`Dispatcher::unsubscribe`] is called
+    /// with the returned id.
+    pub fn subscribe<H: EventHandler + 'static>(&self, handler: H) -> SubscriptionId {
+        self.subscribe_shared(Subscriber::Handler(Arc::new(handler)))
+    }
+
+    /// Subscribe a filter that may consume events delivered through this dispatcher.
+    ///
+    /// The filter stays subscribed until [`Dispatcher::unsubscribe`] is called
+    /// with the returned id.
+    pub fn subscribe_filter<F: EventFilter + 'static>(&self, filter: F) -> SubscriptionId {
+        self.subscribe_shared(Subscriber::Filter(Arc::new(filter)))
+    }
+
+    pub(crate) fn subscribe_shared(&self, subscriber: Subscriber) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.subscribers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((id, subscriber));
+        id
+    }
+
+    /// Remove a previously subscribed handler.
+    ///
+    /// Returns `true` if the subscription existed.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.subscribers.write().unwrap_or_else(PoisonError::into_inner);
+        let before = subscribers.len();
+        subscribers.retain(|(sid, _)| *sid != id);
+        subscribers.len() != before
+    }
+
+    /// Returns the number of currently subscribed handlers.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Set what happens when a subscriber panics while handling an event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::dispatch::{Dispatcher, PanicPolicy};
+    ///
+    /// Dispatcher::global().set_panic_policy(PanicPolicy::DisableHandler);
+    /// ```
+    pub fn set_panic_policy(&self, policy: PanicPolicy) {
+        *lock(&self.panic_policy) = policy;
+    }
+
+    /// Returns what happens when a subscriber panics while handling an event.
+    pub fn panic_policy(&self) -> PanicPolicy {
+        *lock(&self.panic_policy)
+    }
+
+    /// Returns `true` while a hook session is active on this dispatcher.
+    pub(crate) fn is_hook_running(&self) -> bool {
+        self.hook_running.load(Ordering::SeqCst)
+    }
+
+    /// Convert a raw event, deliver it and mark it consumed if a filter asked for it.
+    ///
+    /// libuiohook types characters outside the Basic Multilingual Plane as two
+    /// events carrying a UTF-16 surrogate each. The high surrogate is held back
+    /// (and always propagates) until the low surrogate arrives, and a single
+    /// event with the combined character is delivered. A surrogate without its
+    /// other half is delivered as `char::REPLACEMENT_CHARACTER`.
+    pub(crate) fn dispatch_raw(&self, raw_event: &mut bindings::uiohook_event) -> EventVerdict {
+        let mut event = UiohookEvent::from_raw_event(raw_event);
+
+        let unit = match &event {
+            UiohookEvent::Keyboard(ke) if ke.event_type == KeyboardEventType::Typed => {
+                Some(unsafe { raw_event.data.keyboard.keychar })
+            }
+            _ => None,
+        };
+        let pending = lock(&self.pending_surrogate).take();
+        match (unit, &mut event) {
+            (Some(high @ 0xD800..=0xDBFF), UiohookEvent::Keyboard(ke)) => {
+                *lock(&self.pending_surrogate) = Some((ke.clone(), high));
+                if let Some(lone) = pending {
+                    self.dispatch_lone_surrogate(lone);
+                }
+                return EventVerdict::Propagate;
+            }
+            (Some(low @ 0xDC00..=0xDFFF), UiohookEvent::Keyboard(ke)) => match pending {
+                Some((_, high)) => {
+                    ke.key_char = char::decode_utf16([high, low]).next().and_then(|c| c.ok());
+                }
+                None => {
+                    log::warn!("typed event with a lone UTF-16 surrogate {:#06X}", low);
+                    ke.key_char = Some(char::REPLACEMENT_CHARACTER);
+                }
+            },
+            _ => {
+                if let Some(lone) = pending {
+                    self.dispatch_lone_surrogate(lone);
+                }
+            }
+        }
+
+        let verdict = self.dispatch(&event);
+        if verdict == EventVerdict::Consume {
+            // libuiohook checks this flag after the dispatch procedure returns.
+            raw_event.reserved = EVENT_CONSUMED;
+        }
+        if let UiohookEvent::HookEnabled = event {
+            self.mark_started();
+        }
+        verdict
+    }
+
+    // Deliver a high surrogate that was not followed by a low surrogate.
+    fn dispatch_lone_surrogate(&self, (mut event, unit): (KeyboardEvent, u16)) {
+        log::warn!("typed event with a lone UTF-16 surrogate {:#06X}", unit);
+        event.key_char = Some(char::REPLACEMENT_CHARACTER);
+        self.dispatch(&UiohookEvent::Keyboard(event));
+    }
+
+    /// Deliver an event to every subscriber.
+    ///
+    /// Returns [`EventVerdict::Consume`] if any subscriber consumed the event.
+    pub(crate) fn dispatch(&self, event: &UiohookEvent) -> EventVerdict {
+        // Snapshot the subscribers so handlers may (un)subscribe while being called.
+        let subscribers: Vec<(SubscriptionId, Subscriber)> = self
+            .subscribers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        let mut verdict = EventVerdict::Propagate;
+        let mut stop_hook = false;
+        for (id, subscriber) in subscribers {
+            match panic::catch_unwind(AssertUnwindSafe(|| subscriber.deliver(event))) {
+                Ok(EventVerdict::Consume) => verdict = EventVerdict::Consume,
+                Ok(EventVerdict::Propagate) => {}
+                Err(payload) => {
+                    log::error!(
+                        "subscriber {:?} panicked while handling {:?}: {}",
+                        id,
+                        event,
+                        panic_message(&*payload)
+                    );
+                    match self.panic_policy() {
+                        PanicPolicy::LogAndContinue => {}
+                        PanicPolicy::DisableHandler => {
+                            self.unsubscribe(id);
+                        }
+                        PanicPolicy::StopHook => stop_hook = true,
+                    }
+                }
+            }
+        }
+
+        if stop_hook {
+            self.fail_session(UiohookError::HandlerPanicked, true);
+        }
+        verdict
+    }
+
+    fn lock_session(&self) -> MutexGuard<'_, Session> {
+        lock(&self.session)
+    }
+
+    /// Subscribe a running `Uiohook` and make sure the hook thread is running.
+    ///
+    /// Starts the hook thread for the first member and waits until the backend
+    /// reports `HookEnabled` or fails. `backend` must be the backend whose
+    /// [`HookBackend::dispatcher`] is `self`.
+    pub(crate) fn acquire(
+        &self,
+        backend: Arc<dyn HookBackend>,
+        subscriber: Subscriber,
+        status: Arc<HookStatus>,
+    ) -> Result<SubscriptionId, UiohookError> {
+        let mut session = self.lock_session();
+        let id = self.subscribe_shared(subscriber);
+        session.members.push((id, status));
+        session.backend = Some(Arc::clone(&backend));
+
+        if !matches!(session.state, SessionState::Starting | SessionState::Running) {
+            // Reap the thread of a previous session that died on its own.
+            if let Some(thread) = session.thread.take() {
+                let _ = thread.join();
+            }
+
+            session.state = SessionState::Starting;
+            self.hook_running.store(true, Ordering::SeqCst);
+            session.thread = Some(thread::spawn(move || {
+                let dispatcher = backend.dispatcher();
+                let mut result = Ok(());
+                while dispatcher.is_hook_running() {
+                    if let Err(e) = backend.run() {
+                        result = Err(e);
+                        break;
+                    }
+                }
+                dispatcher.finish(result);
+            }));
+        }
+
+        while session.state == SessionState::Starting {
+            session = self
+                .session_changed
+                .wait(session)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        let error = match session.state {
+            SessionState::Running => return Ok(id),
+            SessionState::Failed(error) => error,
+            _ => UiohookError::Failure,
+        };
+
+        session.members.retain(|(mid, _)| *mid != id);
+        self.unsubscribe(id);
+        let thread = if session.members.is_empty() {
+            session.thread.take()
+        } else {
+            None
+        };
+        drop(session);
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+        Err(error)
+    }
+
+    // Called on the hook thread once the backend reported `HookEnabled`.
+    fn mark_started(&self) {
+        let mut session = self.lock_session();
+        if session.state == SessionState::Starting {
+            session.state = SessionState::Running;
+            self.session_changed.notify_all();
+        }
+    }
+
+    // Called on the hook thread when it exits.
+    fn finish(&self, result: Result<(), UiohookError>) {
+        self.fail_session(result.err().unwrap_or(UiohookError::Failure), false);
+    }
+
+    // End a session that was not stopped on request, reporting `error` to every
+    // member. With `stop_backend`, the hook is still running and must be stopped.
+    fn fail_session(&self, error: UiohookError, stop_backend: bool) {
+        let mut session = self.lock_session();
+        let was_running = match session.state {
+            SessionState::Starting => false,
+            SessionState::Running => true,
+            // Stopped on request by the last member, or already failed.
+            SessionState::Idle | SessionState::Failed(_) => return,
+        };
+
+        session.state = SessionState::Failed(error);
+        self.hook_running.store(false, Ordering::SeqCst);
+        // Members still waiting for startup clean up after themselves in `acquire`.
+        let members = if was_running {
+            std::mem::take(&mut session.members)
+        } else {
+            Vec::new()
+        };
+        let backend = if stop_backend { session.backend.clone() } else { None };
+        drop(session);
+        self.session_changed.notify_all();
+
+        if let Some(backend) = backend {
+            if let Err(e) = backend.stop() {
+                log::error!("failed to stop the hook after a panic: {}", e);
+            }
+        }
+        for (id, status) in members {
+            self.unsubscribe(id);
+            status.fail(error);
+        }
+    }
+
+    /// Unsubscribe a `Uiohook`, stopping the hook thread with the last member.
+    pub(crate) fn release(&self, backend: &dyn HookBackend, id: SubscriptionId) -> Result<(), UiohookError> {
+        let (result, thread) = self.detach(backend, id);
+        if let Some(thread) = thread {
+            thread.join().map_err(|_| UiohookError::Failure)?;
+        }
+        result
+    }
+
+    /// Like `release`, but hands the stopping hook thread back to the caller
+    /// instead of joining it.
+    pub(crate) fn detach(
+        &self,
+        backend: &dyn HookBackend,
+        id: SubscriptionId,
+    ) -> (Result<(), UiohookError>, Option<thread::JoinHandle<()>>) {
+        let mut session = self.lock_session();
+        let Some(index) = session.members.iter().position(|(mid, _)| *mid == id) else {
+            // The hook thread already died and removed every member.
+            return (Ok(()), None);
+        };
+        session.members.remove(index);
+        self.unsubscribe(id);
+        if !session.members.is_empty() {
+            return (Ok(()), None);
+        }
+
+        session.state = SessionState::Idle;
+        self.hook_running.store(false, Ordering::SeqCst);
+        let result = backend.stop();
+        (result, session.thread.take())
+    }
+}
+
+/// Subscribe a handler to every event captured by libuiohook.
+///
+/// Events are only delivered while at least one [`Uiohook`](crate::Uiohook)
+/// is running.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{EventHandler, Uiohook, UiohookEvent};
+/// use uiohook_rs::hook::dispatch::{subscribe, unsubscribe};
+///
+/// struct Logger;
+///
+/// impl EventHandler for Logger {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// struct Ignore;
+///
+/// impl EventHandler for Ignore {
+///     fn handle_event(&self, _event: &UiohookEvent) {}
+/// }
+///
+/// let id = subscribe(Logger);
+/// let hook = Uiohook::new(Ignore);
+/// hook.run().expect("Failed to run uiohook");
+/// // ...
+/// hook.stop().expect("Failed to stop uiohook");
+/// unsubscribe(id);
+/// ```
+pub fn subscribe<H: EventHandler + 'static>(handler: H) -> SubscriptionId {
+    Dispatcher::global().subscribe(handler)
+}
+
+/// Subscribe a filter that may consume events captured by libuiohook.
+///
+/// See [`EventFilter`] for how verdicts from several filters are combined.
+pub fn subscribe_filter<F: EventFilter + 'static>(filter: F) -> SubscriptionId {
+    Dispatcher::global().subscribe_filter(filter)
+}
+
+/// Remove a handler or filter previously registered with [`subscribe`] or
+/// [`subscribe_filter`].
+///
+/// Returns `true` if the subscription existed.
+pub fn unsubscribe(id: SubscriptionId) -> bool {
+    Dispatcher::global().unsubscribe(id)
+}
+
+/// Install `dispatch_proc_wrapper` as libuiohook's dispatch procedure.
+pub(crate) fn install_dispatch_proc() {
+    INSTALL_DISPATCH_PROC.call_once(|| unsafe {
+        bindings::hook_set_dispatch_proc(Some(dispatch_proc_wrapper));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+unsafe extern "C" fn dispatch_proc_wrapper(event: *mut bindings::uiohook_event) {
+    // Unwinding into libuiohook is undefined behavior.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Dispatcher::global().dispatch_raw(&mut *event);
+    }));
+    if let Err(payload) = result {
+        log::error!("panic while dispatching an event: {}", panic_message(&*payload));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::keyboard::KeyCode;
+    use std::sync::atomic::AtomicUsize;
+
+    struct CountingHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl EventHandler for CountingHandler {
+        fn handle_event(&self, _event: &UiohookEvent) {
+            self.count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_every_subscriber_receives_events() {
+        let dispatcher = Dispatcher::new();
+        let first = Arc::new(AtomicUsize::new(0));
+        let second = Arc::new(AtomicUsize::new(0));
+
+        dispatcher.subscribe(CountingHandler { count: first.clone() });
+        dispatcher.subscribe(CountingHandler { count: second.clone() });
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+
+        assert_eq!(first.load(Ordering::SeqCst), 1);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+    }
+
+    struct ConsumeAll;
+
+    impl EventFilter for ConsumeAll {
+        fn filter_event(&self, _event: &UiohookEvent) -> EventVerdict {
+            EventVerdict::Consume
+        }
+    }
+
+    #[test]
+    fn test_any_filter_consumes() {
+        let dispatcher = Dispatcher::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        dispatcher.subscribe(CountingHandler { count: count.clone() });
+        assert_eq!(dispatcher.dispatch(&UiohookEvent::HookEnabled), EventVerdict::Propagate);
+
+        let id = dispatcher.subscribe_filter(ConsumeAll);
+        assert_eq!(dispatcher.dispatch(&UiohookEvent::HookEnabled), EventVerdict::Consume);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        dispatcher.unsubscribe(id);
+        assert_eq!(dispatcher.dispatch(&UiohookEvent::HookEnabled), EventVerdict::Propagate);
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let dispatcher = Dispatcher::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let id = dispatcher.subscribe(CountingHandler { count: count.clone() });
+        assert_eq!(dispatcher.subscriber_count(), 1);
+        assert!(dispatcher.unsubscribe(id));
+        assert!(!dispatcher.unsubscribe(id));
+        assert_eq!(dispatcher.subscriber_count(), 0);
+
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    struct Panicking;
+
+    impl EventHandler for Panicking {
+        fn handle_event(&self, _event: &UiohookEvent) {
+            panic!("handler failure");
+        }
+    }
+
+    #[test]
+    fn test_panicking_handler_is_contained() {
+        let dispatcher = Dispatcher::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let id = dispatcher.subscribe(Panicking);
+        dispatcher.subscribe(CountingHandler { count: count.clone() });
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert_eq!(dispatcher.subscriber_count(), 2);
+
+        dispatcher.set_panic_policy(PanicPolicy::DisableHandler);
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+        assert!(!dispatcher.unsubscribe(id));
+        assert_eq!(dispatcher.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn test_panic_stops_hook() {
+        use crate::hook::backend::{HookBackend, MockBackend};
+        use crate::Uiohook;
+
+        let backend = MockBackend::new();
+        backend.dispatcher().set_panic_policy(PanicPolicy::StopHook);
+        let hook = Uiohook::new(CountingHandler {
+            count: Arc::new(AtomicUsize::new(0)),
+        })
+        .with_backend(backend.clone());
+        hook.run().expect("Failed to run uiohook");
+
+        backend.dispatcher().subscribe(Panicking);
+        backend.inject(&UiohookEvent::HookDisabled);
+
+        assert!(!hook.is_running());
+        assert_eq!(hook.last_error(), Some(UiohookError::HandlerPanicked));
+        assert_eq!(hook.stop(), Err(UiohookError::NotRunning));
+    }
+
+    #[test]
+    fn test_poisoned_subscriber_list_still_delivers() {
+        let dispatcher = Arc::new(Dispatcher::new());
+        let count = Arc::new(AtomicUsize::new(0));
+        dispatcher.subscribe(CountingHandler { count: count.clone() });
+
+        let poisoner = Arc::clone(&dispatcher);
+        let _ = thread::spawn(move || {
+            let _guard = poisoner.subscribers.write().unwrap();
+            panic!("poison the subscriber list");
+        })
+        .join();
+
+        assert!(dispatcher.subscribers.is_poisoned());
+        dispatcher.dispatch(&UiohookEvent::HookEnabled);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    struct TypedChars {
+        chars: Arc<Mutex<Vec<Option<char>>>>,
+    }
+
+    impl EventHandler for TypedChars {
+        fn handle_event(&self, event: &UiohookEvent) {
+            if let UiohookEvent::Keyboard(ke) = event {
+                self.chars.lock().unwrap().push(ke.key_char);
+            }
+        }
+    }
+
+    fn raw_typed(unit: u16) -> bindings::uiohook_event {
+        let mut raw_event = UiohookEvent::Keyboard(KeyboardEvent {
+            event_type: KeyboardEventType::Typed,
+            key_code: KeyCode::Undefined,
+            raw_code: 0,
+            key_char: None,
+            meta: Default::default(),
+        })
+        .to_raw_event();
+        raw_event.data.keyboard.keychar = unit;
+        raw_event
+    }
+
+    #[test]
+    fn test_surrogate_pairs_are_assembled() {
+        let dispatcher = Dispatcher::new();
+        let chars = Arc::new(Mutex::new(Vec::new()));
+        dispatcher.subscribe(TypedChars { chars: chars.clone() });
+
+        for unit in [0xD83D, 0xDE00, 'a' as u16] {
+            dispatcher.dispatch_raw(&mut raw_typed(unit));
+        }
+        assert_eq!(*chars.lock().unwrap(), vec![Some('😀'), Some('a')]);
+    }
+
+    #[test]
+    fn test_lone_surrogates_are_reported() {
+        let dispatcher = Dispatcher::new();
+        let chars = Arc::new(Mutex::new(Vec::new()));
+        dispatcher.subscribe(TypedChars { chars: chars.clone() });
+
+        // A low surrogate on its own, a high surrogate followed by another one,
+        // and a high surrogate interrupted by a non-typed event.
+        dispatcher.dispatch_raw(&mut raw_typed(0xDE00));
+        dispatcher.dispatch_raw(&mut raw_typed(0xD83D));
+        dispatcher.dispatch_raw(&mut raw_typed(0xD83D));
+        dispatcher.dispatch_raw(&mut raw_typed(0xDE00));
+        dispatcher.dispatch_raw(&mut raw_typed(0xD83D));
+        dispatcher.dispatch_raw(&mut UiohookEvent::HookDisabled.to_raw_event());
+
+        let replacement = Some(char::REPLACEMENT_CHARACTER);
+        assert_eq!(*chars.lock().unwrap(), vec![replacement, replacement, Some('😀'), replacement]);
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{bindings, UiohookEvent};
+use crate::{bindings, EventMeta, UiohookEvent};
 use crate::error::UiohookError;
 use crate::Uiohook;
 use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 
 /// Represents the type of keyboard event.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,15 +29,35 @@ pub struct KeyboardEvent {
     pub raw_code: u16,
     /// The character associated with the key, if applicable.
     pub key_char: Option<char>,
+    /// The timestamp and modifier state of the event.
+    pub meta: EventMeta,
 }
 
 impl From<&bindings::keyboard_event_data> for KeyboardEvent {
     fn from(event: &bindings::keyboard_event_data) -> Self {
         KeyboardEvent {
             event_type: KeyboardEventType::Pressed, // This will be set correctly by the caller
-            key_code: KeyCode::try_from(event.keycode as u32).unwrap_or(KeyCode::Undefined),
+            key_code: KeyCode::try_from(event.keycode as u32).unwrap_or(KeyCode::Other(event.keycode)),
             raw_code: event.rawcode,
-            key_char: char::from_u32(event.keychar as u32),
+            key_char: match event.keychar as u32 {
+                bindings::CHAR_UNDEFINED => None,
+                keychar => char::from_u32(keychar),
+            },
+            meta: EventMeta::default(), // This will be set correctly by the caller
+        }
+    }
+}
+
+impl From<&KeyboardEvent> for bindings::keyboard_event_data {
+    fn from(event: &KeyboardEvent) -> Self {
+        bindings::keyboard_event_data {
+            keycode: u32::from(event.key_code) as u16,
+            rawcode: event.raw_code,
+            // Characters outside the Basic Multilingual Plane do not fit in a single keychar.
+            keychar: event
+                .key_char
+                .and_then(|c| u16::try_from(u32::from(c)).ok())
+                .unwrap_or(bindings::CHAR_UNDEFINED as u16),
         }
     }
 }
@@ -94,10 +118,16 @@ pub enum KeyCode {
     
     // Undefined key
     Undefined, CharUndefined,
+
+    /// A key code without a named variant, such as a vendor specific key.
+    ///
+    /// Holds the raw `VC_*` value so the key survives being captured and posted
+    /// again. Codes that do have a named variant are never captured as `Other`.
+    Other(u16),
 }
 
 impl TryFrom<u32> for KeyCode {
-    type Error = ();
+    type Error = UiohookError;
 
     fn try_from(value: u32) -> Result<Self, Self::Error> {
         match value {
@@ -274,7 +304,7 @@ impl TryFrom<u32> for KeyCode {
             bindings::VC_SUN_CUT => Ok(KeyCode::SunCut),
             bindings::VC_UNDEFINED => Ok(KeyCode::Undefined),
             bindings::CHAR_UNDEFINED => Ok(KeyCode::CharUndefined),
-            _ => Err(()),
+            _ => Err(UiohookError::UnknownKeyCode(value)),
         }
     }
 }
@@ -455,10 +485,450 @@ impl From<KeyCode> for u32 {
             KeyCode::SunCut => bindings::VC_SUN_CUT,
             KeyCode::Undefined => bindings::VC_UNDEFINED,
             KeyCode::CharUndefined => bindings::CHAR_UNDEFINED,
+            KeyCode::Other(code) => code as u32,
         }
     }
 }
 
+impl KeyCode {
+    // Every named key code, in declaration order.
+    const ALL: [KeyCode; 173] = [
+        // Function keys
+        KeyCode::Escape,
+        KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6, KeyCode::F7,
+        KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+        KeyCode::F13, KeyCode::F14, KeyCode::F15, KeyCode::F16, KeyCode::F17, KeyCode::F18,
+        KeyCode::F19, KeyCode::F20, KeyCode::F21, KeyCode::F22, KeyCode::F23, KeyCode::F24,
+
+        // Alphanumeric keys
+        KeyCode::Backquote,
+        KeyCode::Num1, KeyCode::Num2, KeyCode::Num3, KeyCode::Num4, KeyCode::Num5, KeyCode::Num6,
+        KeyCode::Num7, KeyCode::Num8, KeyCode::Num9, KeyCode::Num0,
+        KeyCode::Minus, KeyCode::Equals, KeyCode::Backspace,
+        KeyCode::Tab, KeyCode::CapsLock,
+        KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+        KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M,
+        KeyCode::N, KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T,
+        KeyCode::U, KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+        KeyCode::OpenBracket, KeyCode::CloseBracket, KeyCode::Backslash,
+        KeyCode::Semicolon, KeyCode::Quote, KeyCode::Enter,
+        KeyCode::Comma, KeyCode::Period, KeyCode::Slash,
+        KeyCode::Space,
+
+        // Navigation keys
+        KeyCode::PrintScreen, KeyCode::ScrollLock, KeyCode::Pause, KeyCode::LesserGreater,
+        KeyCode::Insert, KeyCode::Delete, KeyCode::Home, KeyCode::End, KeyCode::PageUp,
+        KeyCode::PageDown,
+        KeyCode::Up, KeyCode::Left, KeyCode::Clear, KeyCode::Right, KeyCode::Down,
+
+        // Numeric keypad
+        KeyCode::NumLock,
+        KeyCode::KpDivide, KeyCode::KpMultiply, KeyCode::KpSubtract, KeyCode::KpEquals,
+        KeyCode::KpAdd, KeyCode::KpEnter, KeyCode::KpSeparator,
+        KeyCode::Kp1, KeyCode::Kp2, KeyCode::Kp3, KeyCode::Kp4, KeyCode::Kp5, KeyCode::Kp6,
+        KeyCode::Kp7, KeyCode::Kp8, KeyCode::Kp9, KeyCode::Kp0,
+        KeyCode::KpEnd, KeyCode::KpDown, KeyCode::KpPageDown, KeyCode::KpLeft, KeyCode::KpClear,
+        KeyCode::KpRight, KeyCode::KpHome, KeyCode::KpUp, KeyCode::KpPageUp,
+        KeyCode::KpInsert, KeyCode::KpDelete,
+
+        // Modifier keys
+        KeyCode::ShiftL, KeyCode::ShiftR, KeyCode::ControlL, KeyCode::ControlR, KeyCode::AltL,
+        KeyCode::AltR, KeyCode::MetaL, KeyCode::MetaR,
+
+        // Additional keys
+        KeyCode::ContextMenu, KeyCode::Power, KeyCode::Sleep, KeyCode::Wake,
+
+        // Media keys
+        KeyCode::MediaPlay, KeyCode::MediaStop, KeyCode::MediaPrevious, KeyCode::MediaNext,
+        KeyCode::MediaSelect, KeyCode::MediaEject,
+        KeyCode::VolumeMute, KeyCode::VolumeUp, KeyCode::VolumeDown,
+
+        // Application keys
+        KeyCode::AppMail, KeyCode::AppCalculator, KeyCode::AppMusic, KeyCode::AppPictures,
+
+        // Browser keys
+        KeyCode::BrowserSearch, KeyCode::BrowserHome, KeyCode::BrowserBack, KeyCode::BrowserForward,
+        KeyCode::BrowserStop, KeyCode::BrowserRefresh, KeyCode::BrowserFavorites,
+
+        // Japanese keys
+        KeyCode::Katakana, KeyCode::Underscore, KeyCode::Furigana, KeyCode::Kanji,
+        KeyCode::Hiragana, KeyCode::Yen, KeyCode::KpComma,
+
+        // Sun keys
+        KeyCode::SunHelp, KeyCode::SunStop, KeyCode::SunProps, KeyCode::SunFront, KeyCode::SunOpen,
+        KeyCode::SunFind, KeyCode::SunAgain, KeyCode::SunUndo, KeyCode::SunCopy, KeyCode::SunInsert,
+        KeyCode::SunCut,
+
+        // Undefined key
+        KeyCode::Undefined, KeyCode::CharUndefined,
+    ];
+
+    /// Returns an iterator over every named key code, excluding [`KeyCode::Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::keyboard::KeyCode;
+    ///
+    /// let function_keys = KeyCode::all().filter(|key| key.is_function()).count();
+    /// assert_eq!(function_keys, 24);
+    /// ```
+    pub fn all() -> impl ExactSizeIterator<Item = KeyCode> + Clone {
+        Self::ALL.iter().copied()
+    }
+
+    /// Returns the human-readable name of the key, such as `"Left Shift"`.
+    ///
+    /// [`KeyCode::Other`] is named `"Other"`; its `Display` form includes the code.
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyCode::Escape => "Escape",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::F13 => "F13",
+            KeyCode::F14 => "F14",
+            KeyCode::F15 => "F15",
+            KeyCode::F16 => "F16",
+            KeyCode::F17 => "F17",
+            KeyCode::F18 => "F18",
+            KeyCode::F19 => "F19",
+            KeyCode::F20 => "F20",
+            KeyCode::F21 => "F21",
+            KeyCode::F22 => "F22",
+            KeyCode::F23 => "F23",
+            KeyCode::F24 => "F24",
+            KeyCode::Backquote => "Backquote",
+            KeyCode::Num1 => "1",
+            KeyCode::Num2 => "2",
+            KeyCode::Num3 => "3",
+            KeyCode::Num4 => "4",
+            KeyCode::Num5 => "5",
+            KeyCode::Num6 => "6",
+            KeyCode::Num7 => "7",
+            KeyCode::Num8 => "8",
+            KeyCode::Num9 => "9",
+            KeyCode::Num0 => "0",
+            KeyCode::Minus => "Minus",
+            KeyCode::Equals => "Equals",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Tab => "Tab",
+            KeyCode::CapsLock => "Caps Lock",
+            KeyCode::A => "A",
+            KeyCode::B => "B",
+            KeyCode::C => "C",
+            KeyCode::D => "D",
+            KeyCode::E => "E",
+            KeyCode::F => "F",
+            KeyCode::G => "G",
+            KeyCode::H => "H",
+            KeyCode::I => "I",
+            KeyCode::J => "J",
+            KeyCode::K => "K",
+            KeyCode::L => "L",
+            KeyCode::M => "M",
+            KeyCode::N => "N",
+            KeyCode::O => "O",
+            KeyCode::P => "P",
+            KeyCode::Q => "Q",
+            KeyCode::R => "R",
+            KeyCode::S => "S",
+            KeyCode::T => "T",
+            KeyCode::U => "U",
+            KeyCode::V => "V",
+            KeyCode::W => "W",
+            KeyCode::X => "X",
+            KeyCode::Y => "Y",
+            KeyCode::Z => "Z",
+            KeyCode::OpenBracket => "Open Bracket",
+            KeyCode::CloseBracket => "Close Bracket",
+            KeyCode::Backslash => "Backslash",
+            KeyCode::Semicolon => "Semicolon",
+            KeyCode::Quote => "Quote",
+            KeyCode::Enter => "Enter",
+            KeyCode::Comma => "Comma",
+            KeyCode::Period => "Period",
+            KeyCode::Slash => "Slash",
+            KeyCode::Space => "Space",
+            KeyCode::PrintScreen => "Print Screen",
+            KeyCode::ScrollLock => "Scroll Lock",
+            KeyCode::Pause => "Pause",
+            KeyCode::LesserGreater => "Lesser Greater",
+            KeyCode::Insert => "Insert",
+            KeyCode::Delete => "Delete",
+            KeyCode::Home => "Home",
+            KeyCode::End => "End",
+            KeyCode::PageUp => "Page Up",
+            KeyCode::PageDown => "Page Down",
+            KeyCode::Up => "Up",
+            KeyCode::Left => "Left",
+            KeyCode::Clear => "Clear",
+            KeyCode::Right => "Right",
+            KeyCode::Down => "Down",
+            KeyCode::NumLock => "Num Lock",
+            KeyCode::KpDivide => "Keypad Divide",
+            KeyCode::KpMultiply => "Keypad Multiply",
+            KeyCode::KpSubtract => "Keypad Subtract",
+            KeyCode::KpEquals => "Keypad Equals",
+            KeyCode::KpAdd => "Keypad Add",
+            KeyCode::KpEnter => "Keypad Enter",
+            KeyCode::KpSeparator => "Keypad Separator",
+            KeyCode::Kp1 => "Keypad 1",
+            KeyCode::Kp2 => "Keypad 2",
+            KeyCode::Kp3 => "Keypad 3",
+            KeyCode::Kp4 => "Keypad 4",
+            KeyCode::Kp5 => "Keypad 5",
+            KeyCode::Kp6 => "Keypad 6",
+            KeyCode::Kp7 => "Keypad 7",
+            KeyCode::Kp8 => "Keypad 8",
+            KeyCode::Kp9 => "Keypad 9",
+            KeyCode::Kp0 => "Keypad 0",
+            KeyCode::KpEnd => "Keypad End",
+            KeyCode::KpDown => "Keypad Down",
+            KeyCode::KpPageDown => "Keypad Page Down",
+            KeyCode::KpLeft => "Keypad Left",
+            KeyCode::KpClear => "Keypad Clear",
+            KeyCode::KpRight => "Keypad Right",
+            KeyCode::KpHome => "Keypad Home",
+            KeyCode::KpUp => "Keypad Up",
+            KeyCode::KpPageUp => "Keypad Page Up",
+            KeyCode::KpInsert => "Keypad Insert",
+            KeyCode::KpDelete => "Keypad Delete",
+            KeyCode::ShiftL => "Left Shift",
+            KeyCode::ShiftR => "Right Shift",
+            KeyCode::ControlL => "Left Control",
+            KeyCode::ControlR => "Right Control",
+            KeyCode::AltL => "Left Alt",
+            KeyCode::AltR => "Right Alt",
+            KeyCode::MetaL => "Left Meta",
+            KeyCode::MetaR => "Right Meta",
+            KeyCode::ContextMenu => "Context Menu",
+            KeyCode::Power => "Power",
+            KeyCode::Sleep => "Sleep",
+            KeyCode::Wake => "Wake",
+            KeyCode::MediaPlay => "Media Play",
+            KeyCode::MediaStop => "Media Stop",
+            KeyCode::MediaPrevious => "Media Previous",
+            KeyCode::MediaNext => "Media Next",
+            KeyCode::MediaSelect => "Media Select",
+            KeyCode::MediaEject => "Media Eject",
+            KeyCode::VolumeMute => "Volume Mute",
+            KeyCode::VolumeUp => "Volume Up",
+            KeyCode::VolumeDown => "Volume Down",
+            KeyCode::AppMail => "App Mail",
+            KeyCode::AppCalculator => "App Calculator",
+            KeyCode::AppMusic => "App Music",
+            KeyCode::AppPictures => "App Pictures",
+            KeyCode::BrowserSearch => "Browser Search",
+            KeyCode::BrowserHome => "Browser Home",
+            KeyCode::BrowserBack => "Browser Back",
+            KeyCode::BrowserForward => "Browser Forward",
+            KeyCode::BrowserStop => "Browser Stop",
+            KeyCode::BrowserRefresh => "Browser Refresh",
+            KeyCode::BrowserFavorites => "Browser Favorites",
+            KeyCode::Katakana => "Katakana",
+            KeyCode::Underscore => "Underscore",
+            KeyCode::Furigana => "Furigana",
+            KeyCode::Kanji => "Kanji",
+            KeyCode::Hiragana => "Hiragana",
+            KeyCode::Yen => "Yen",
+            KeyCode::KpComma => "Keypad Comma",
+            KeyCode::SunHelp => "Sun Help",
+            KeyCode::SunStop => "Sun Stop",
+            KeyCode::SunProps => "Sun Props",
+            KeyCode::SunFront => "Sun Front",
+            KeyCode::SunOpen => "Sun Open",
+            KeyCode::SunFind => "Sun Find",
+            KeyCode::SunAgain => "Sun Again",
+            KeyCode::SunUndo => "Sun Undo",
+            KeyCode::SunCopy => "Sun Copy",
+            KeyCode::SunInsert => "Sun Insert",
+            KeyCode::SunCut => "Sun Cut",
+            KeyCode::Undefined => "Undefined",
+            KeyCode::CharUndefined => "Char Undefined",
+            KeyCode::Other(_) => "Other",
+        }
+    }
+
+    /// Checks if the key is a Shift, Control, Alt or Meta key.
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::ShiftL | KeyCode::ShiftR | KeyCode::ControlL | KeyCode::ControlR
+                | KeyCode::AltL | KeyCode::AltR | KeyCode::MetaL | KeyCode::MetaR
+        )
+    }
+
+    /// Checks if the key is one of the function keys F1 to F24.
+    pub fn is_function(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::F1 | KeyCode::F2 | KeyCode::F3 | KeyCode::F4 | KeyCode::F5 | KeyCode::F6
+                | KeyCode::F7 | KeyCode::F8 | KeyCode::F9 | KeyCode::F10 | KeyCode::F11 | KeyCode::F12
+                | KeyCode::F13 | KeyCode::F14 | KeyCode::F15 | KeyCode::F16 | KeyCode::F17 | KeyCode::F18
+                | KeyCode::F19 | KeyCode::F20 | KeyCode::F21 | KeyCode::F22 | KeyCode::F23 | KeyCode::F24
+        )
+    }
+
+    /// Checks if the key is on the numeric keypad, including Num Lock.
+    pub fn is_keypad(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::NumLock
+                | KeyCode::KpDivide | KeyCode::KpMultiply | KeyCode::KpSubtract | KeyCode::KpEquals
+                | KeyCode::KpAdd | KeyCode::KpEnter | KeyCode::KpSeparator | KeyCode::KpComma
+                | KeyCode::Kp1 | KeyCode::Kp2 | KeyCode::Kp3 | KeyCode::Kp4 | KeyCode::Kp5
+                | KeyCode::Kp6 | KeyCode::Kp7 | KeyCode::Kp8 | KeyCode::Kp9 | KeyCode::Kp0
+                | KeyCode::KpEnd | KeyCode::KpDown | KeyCode::KpPageDown | KeyCode::KpLeft
+                | KeyCode::KpClear | KeyCode::KpRight | KeyCode::KpHome | KeyCode::KpUp
+                | KeyCode::KpPageUp | KeyCode::KpInsert | KeyCode::KpDelete
+        )
+    }
+
+    /// Checks if the key is a media playback or volume key.
+    pub fn is_media(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::MediaPlay | KeyCode::MediaStop | KeyCode::MediaPrevious | KeyCode::MediaNext
+                | KeyCode::MediaSelect | KeyCode::MediaEject
+                | KeyCode::VolumeMute | KeyCode::VolumeUp | KeyCode::VolumeDown
+        )
+    }
+}
+
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyCode::Other(code) => f.pad(&format!("0x{:04X}", code)),
+            _ => f.pad(self.name()),
+        }
+    }
+}
+
+// Alternative spellings accepted by `KeyCode::from_str`, in normalized form.
+const KEY_ALIASES: &[(&str, KeyCode)] = &[
+    ("esc", KeyCode::Escape),
+    ("grave", KeyCode::Backquote),
+    ("`", KeyCode::Backquote),
+    ("-", KeyCode::Minus),
+    ("=", KeyCode::Equals),
+    ("bksp", KeyCode::Backspace),
+    ("caps", KeyCode::CapsLock),
+    ("[", KeyCode::OpenBracket),
+    ("]", KeyCode::CloseBracket),
+    ("\\", KeyCode::Backslash),
+    (";", KeyCode::Semicolon),
+    ("'", KeyCode::Quote),
+    ("return", KeyCode::Enter),
+    (",", KeyCode::Comma),
+    (".", KeyCode::Period),
+    ("/", KeyCode::Slash),
+    ("spacebar", KeyCode::Space),
+    ("prtsc", KeyCode::PrintScreen),
+    ("scrlk", KeyCode::ScrollLock),
+    ("break", KeyCode::Pause),
+    ("ins", KeyCode::Insert),
+    ("del", KeyCode::Delete),
+    ("pgup", KeyCode::PageUp),
+    ("pgdn", KeyCode::PageDown),
+    ("pgdown", KeyCode::PageDown),
+    ("arrowup", KeyCode::Up),
+    ("arrowleft", KeyCode::Left),
+    ("arrowright", KeyCode::Right),
+    ("arrowdown", KeyCode::Down),
+    ("numlk", KeyCode::NumLock),
+    ("shift", KeyCode::ShiftL),
+    ("lshift", KeyCode::ShiftL),
+    ("rshift", KeyCode::ShiftR),
+    ("ctrl", KeyCode::ControlL),
+    ("control", KeyCode::ControlL),
+    ("lctrl", KeyCode::ControlL),
+    ("leftctrl", KeyCode::ControlL),
+    ("rctrl", KeyCode::ControlR),
+    ("rightctrl", KeyCode::ControlR),
+    ("alt", KeyCode::AltL),
+    ("option", KeyCode::AltL),
+    ("lalt", KeyCode::AltL),
+    ("ralt", KeyCode::AltR),
+    ("altgr", KeyCode::AltR),
+    ("meta", KeyCode::MetaL),
+    ("super", KeyCode::MetaL),
+    ("win", KeyCode::MetaL),
+    ("cmd", KeyCode::MetaL),
+    ("command", KeyCode::MetaL),
+    ("lmeta", KeyCode::MetaL),
+    ("rmeta", KeyCode::MetaR),
+    ("menu", KeyCode::ContextMenu),
+    ("apps", KeyCode::ContextMenu),
+    ("playpause", KeyCode::MediaPlay),
+    ("mute", KeyCode::VolumeMute),
+    ("volup", KeyCode::VolumeUp),
+    ("voldown", KeyCode::VolumeDown),
+];
+
+// Lowercase `name` and drop separators, so "Page Up", "page_up" and "PageUp" compare equal.
+pub(crate) fn normalize_key_name(name: &str) -> String {
+    let name = name.trim();
+    if name.chars().count() == 1 {
+        return name.to_lowercase();
+    }
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect();
+    match name.strip_prefix("numpad") {
+        Some(rest) => format!("kp{}", rest),
+        None => name,
+    }
+}
+
+impl FromStr for KeyCode {
+    type Err = UiohookError;
+
+    /// Parses a key from its name, its variant name or a common alias.
+    ///
+    /// Matching ignores case, spaces, underscores and hyphens, so `"Left Shift"`,
+    /// `"ShiftL"` and `"lshift"` all parse. Aliases include `"ctrl"`, `"esc"`,
+    /// `"pgup"` and `"kp_5"`. A hexadecimal `VC_*` value such as `"0xE0F0"`
+    /// parses to the matching key, or to [`KeyCode::Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::keyboard::KeyCode;
+    ///
+    /// assert_eq!("ctrl".parse::<KeyCode>(), Ok(KeyCode::ControlL));
+    /// assert_eq!("Page Up".parse::<KeyCode>(), Ok(KeyCode::PageUp));
+    /// assert_eq!("kp_5".parse::<KeyCode>(), Ok(KeyCode::Kp5));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = UiohookError::InvalidArgument("unknown key name");
+        if let Some(hex) = s.trim().strip_prefix("0x").or_else(|| s.trim().strip_prefix("0X")) {
+            let code = u16::from_str_radix(hex, 16).map_err(|_| unknown)?;
+            return Ok(KeyCode::try_from(code as u32).unwrap_or(KeyCode::Other(code)));
+        }
+
+        let name = normalize_key_name(s);
+        KeyCode::all()
+            .find(|key| {
+                normalize_key_name(key.name()) == name
+                    || normalize_key_name(&format!("{:?}", key)) == name
+            })
+            .or_else(|| KEY_ALIASES.iter().find(|(alias, _)| *alias == name).map(|&(_, key)| key))
+            .ok_or(unknown)
+    }
+}
+
 /// Simulates a key tap (press and release) for the given key code.
 ///
 /// # Arguments
@@ -474,7 +944,7 @@ impl From<KeyCode> for u32 {
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, keyboard::{key_tap, KeyCode}};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::keyboard::{key_tap, KeyCode}};
 ///
 /// struct MyHandler;
 ///
@@ -526,7 +996,7 @@ pub fn key_tap(uiohook: &Uiohook, key: KeyCode, modifiers: &[KeyCode]) -> Result
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, keyboard::{key_toggle, KeyCode}};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::keyboard::{key_toggle, KeyCode}};
 ///
 /// struct MyHandler;
 ///
@@ -540,7 +1010,6 @@ pub fn key_tap(uiohook: &Uiohook, key: KeyCode, modifiers: &[KeyCode]) -> Result
 /// key_toggle(&hook, KeyCode::ShiftL, true).expect("Failed to press key");
 /// key_toggle(&hook, KeyCode::ShiftL, false).expect("Failed to release key");
 /// ```
-
 pub fn key_toggle(uiohook: &Uiohook, key: KeyCode, down: bool) -> Result<(), UiohookError> {
     // 1. Create a keyboard event for pressing or releasing the key based on the 'down' parameter
     let event_type = if down { KeyboardEventType::Pressed } else { KeyboardEventType::Released };
@@ -553,6 +1022,255 @@ pub fn key_toggle(uiohook: &Uiohook, key: KeyCode, down: bool) -> Result<(), Uio
 }
 
 
+/// Maps characters to the keys that type them.
+///
+/// [`type_text_with_layout`] taps the key a layout returns for each character
+/// and posts characters the layout has no key for as typed events. Closures
+/// with the same signature as [`KeyboardLayout::key_for`] are layouts too, so
+/// a few characters can be remapped on top of [`UsLayout`].
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::{KeyCode, KeyboardLayout, UsLayout};
+///
+/// // German QWERTZ swaps Y and Z.
+/// let qwertz = |c: char| match c {
+///     'y' => Some((KeyCode::Z, &[][..])),
+///     'z' => Some((KeyCode::Y, &[][..])),
+///     _ => UsLayout.key_for(c),
+/// };
+/// assert_eq!(qwertz.key_for('z'), Some((KeyCode::Y, &[][..])));
+/// assert_eq!(qwertz.key_for('A'), Some((KeyCode::A, &[KeyCode::ShiftL][..])));
+/// ```
+pub trait KeyboardLayout {
+    /// Returns the key that types `c` and the modifier keys to hold while
+    /// tapping it, or `None` if no key on the layout types `c`.
+    fn key_for(&self, c: char) -> Option<(KeyCode, &'static [KeyCode])>;
+}
+
+impl<F> KeyboardLayout for F
+where
+    F: Fn(char) -> Option<(KeyCode, &'static [KeyCode])>,
+{
+    fn key_for(&self, c: char) -> Option<(KeyCode, &'static [KeyCode])> {
+        self(c)
+    }
+}
+
+/// The US QWERTY layout, covering printable ASCII, tab and line breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UsLayout;
+
+impl KeyboardLayout for UsLayout {
+    fn key_for(&self, c: char) -> Option<(KeyCode, &'static [KeyCode])> {
+        match us_layout_key(c)? {
+            (key, false) => Some((key, &[])),
+            (key, true) => Some((key, &[KeyCode::ShiftL])),
+        }
+    }
+}
+
+/// Simulates typing a string of text.
+///
+/// ASCII characters are typed by tapping their key on a US QWERTY layout,
+/// holding Shift where needed. Any other character is posted as a typed event
+/// carrying the character itself; characters outside the Basic Multilingual
+/// Plane are posted as a UTF-16 surrogate pair. Use [`type_text_with_layout`]
+/// for other layouts.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `text` - The text to type.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::keyboard::type_text};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// type_text(&hook, "Grüße ✓").expect("Failed to type text");
+/// ```
+pub fn type_text(uiohook: &Uiohook, text: &str) -> Result<(), UiohookError> {
+    type_text_with_layout(uiohook, text, &UsLayout, Duration::ZERO)
+}
+
+/// Simulates typing a string of text, pausing between characters.
+///
+/// Behaves like [`type_text`], for applications that drop keys typed too quickly.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `text` - The text to type.
+/// * `delay` - The pause between consecutive characters.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::keyboard::type_text_with_delay};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// type_text_with_delay(&hook, "hello", Duration::from_millis(20)).expect("Failed to type text");
+/// ```
+pub fn type_text_with_delay(uiohook: &Uiohook, text: &str, delay: Duration) -> Result<(), UiohookError> {
+    type_text_with_layout(uiohook, text, &UsLayout, delay)
+}
+
+/// Simulates typing a string of text through a keyboard layout.
+///
+/// Each character is typed by tapping the key `layout` returns for it while
+/// holding the returned modifiers. Characters the layout has no key for are
+/// posted as typed events, like [`type_text`] does. A `"\r\n"` line break is
+/// typed as a single Enter.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `text` - The text to type.
+/// * `layout` - The layout mapping characters to keys.
+/// * `delay` - The pause between consecutive characters.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+/// use uiohook_rs::hook::keyboard::{type_text_with_layout, KeyCode, KeyboardLayout, UsLayout};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// // French AZERTY types 'a' with the key in the US 'q' position.
+/// let azerty = |c: char| match c {
+///     'a' => Some((KeyCode::Q, &[][..])),
+///     'q' => Some((KeyCode::A, &[][..])),
+///     _ => UsLayout.key_for(c),
+/// };
+///
+/// let hook = Uiohook::new(MyHandler);
+/// type_text_with_layout(&hook, "quoi", &azerty, Duration::ZERO).expect("Failed to type text");
+/// ```
+pub fn type_text_with_layout<L: KeyboardLayout + ?Sized>(
+    uiohook: &Uiohook,
+    text: &str,
+    layout: &L,
+    delay: Duration,
+) -> Result<(), UiohookError> {
+    let mut previous = None;
+    for (i, c) in text.chars().enumerate() {
+        if c == '\n' && previous == Some('\r') {
+            previous = Some(c);
+            continue;
+        }
+        previous = Some(c);
+        if i > 0 && !delay.is_zero() {
+            thread::sleep(delay);
+        }
+        match layout.key_for(c) {
+            Some((key, modifiers)) => key_tap(uiohook, key, modifiers)?,
+            None => {
+                let mut event = create_keyboard_event(KeyboardEventType::Typed, KeyCode::Undefined);
+                event.key_char = Some(c);
+                uiohook.post_event(&UiohookEvent::Keyboard(event))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// The key producing `c` on a US QWERTY layout, and whether Shift is needed.
+pub(crate) fn us_layout_key(c: char) -> Option<(KeyCode, bool)> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+        KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M,
+        KeyCode::N, KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T,
+        KeyCode::U, KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Num0, KeyCode::Num1, KeyCode::Num2, KeyCode::Num3, KeyCode::Num4,
+        KeyCode::Num5, KeyCode::Num6, KeyCode::Num7, KeyCode::Num8, KeyCode::Num9,
+    ];
+
+    let key = match c {
+        'a'..='z' => (LETTERS[c as usize - 'a' as usize], false),
+        'A'..='Z' => (LETTERS[c as usize - 'A' as usize], true),
+        '0'..='9' => (DIGITS[c as usize - '0' as usize], false),
+        ')' => (KeyCode::Num0, true),
+        '!' => (KeyCode::Num1, true),
+        '@' => (KeyCode::Num2, true),
+        '#' => (KeyCode::Num3, true),
+        '$' => (KeyCode::Num4, true),
+        '%' => (KeyCode::Num5, true),
+        '^' => (KeyCode::Num6, true),
+        '&' => (KeyCode::Num7, true),
+        '*' => (KeyCode::Num8, true),
+        '(' => (KeyCode::Num9, true),
+        '`' => (KeyCode::Backquote, false),
+        '~' => (KeyCode::Backquote, true),
+        '-' => (KeyCode::Minus, false),
+        '_' => (KeyCode::Minus, true),
+        '=' => (KeyCode::Equals, false),
+        '+' => (KeyCode::Equals, true),
+        '[' => (KeyCode::OpenBracket, false),
+        '{' => (KeyCode::OpenBracket, true),
+        ']' => (KeyCode::CloseBracket, false),
+        '}' => (KeyCode::CloseBracket, true),
+        '\\' => (KeyCode::Backslash, false),
+        '|' => (KeyCode::Backslash, true),
+        ';' => (KeyCode::Semicolon, false),
+        ':' => (KeyCode::Semicolon, true),
+        '\'' => (KeyCode::Quote, false),
+        '"' => (KeyCode::Quote, true),
+        ',' => (KeyCode::Comma, false),
+        '<' => (KeyCode::Comma, true),
+        '.' => (KeyCode::Period, false),
+        '>' => (KeyCode::Period, true),
+        '/' => (KeyCode::Slash, false),
+        '?' => (KeyCode::Slash, true),
+        ' ' => (KeyCode::Space, false),
+        '\t' => (KeyCode::Tab, false),
+        '\n' | '\r' => (KeyCode::Enter, false),
+        _ => return None,
+    };
+    Some(key)
+}
+
 // Helper function to create a KeyboardEvent
 fn create_keyboard_event(event_type: KeyboardEventType, key: KeyCode) -> KeyboardEvent {
     KeyboardEvent {
@@ -560,6 +1278,7 @@ fn create_keyboard_event(event_type: KeyboardEventType, key: KeyCode) -> Keyboar
         key_code: key,
         raw_code: u32::from(key) as u16, // Cast to u16 as raw_code is u16
         key_char: None, // We don't have character information for simulated events
+        meta: EventMeta::default(),
     }
 }
 
@@ -567,12 +1286,14 @@ fn create_keyboard_event(event_type: KeyboardEventType, key: KeyCode) -> Keyboar
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+
 
     #[test]
     fn test_key_code_conversion() {
         assert_eq!(KeyCode::try_from(bindings::VC_ESCAPE), Ok(KeyCode::Escape));
         assert_eq!(KeyCode::try_from(bindings::VC_F1), Ok(KeyCode::F1));
-        assert_eq!(KeyCode::try_from(0xFFFFFFFF), Err(()));
+        assert_eq!(KeyCode::try_from(0xFFFFFFFF), Err(UiohookError::UnknownKeyCode(0xFFFFFFFF)));
 
         assert_eq!(u32::from(KeyCode::Escape), bindings::VC_ESCAPE);
         assert_eq!(u32::from(KeyCode::F1), bindings::VC_F1);
@@ -592,5 +1313,230 @@ mod tests {
         assert_eq!(event.key_char, Some('A'));
     }
 
+    #[test]
+    fn test_every_key_code_round_trips() {
+        for key in KeyCode::all() {
+            let raw = u32::from(key);
+            assert!(raw <= u16::MAX as u32, "{:?} does not fit in a keycode", key);
+            assert_eq!(KeyCode::try_from(raw), Ok(key), "{:?} does not round trip", key);
+        }
+
+        // Every keycode libuiohook can report maps back to itself, and to a listed variant.
+        let mut seen = HashSet::new();
+        for raw in 0..=u16::MAX as u32 {
+            if let Ok(key) = KeyCode::try_from(raw) {
+                assert_eq!(u32::from(key), raw);
+                assert!(KeyCode::ALL.contains(&key), "{:?} is missing from the list", key);
+                seen.insert(key);
+            }
+        }
+        assert_eq!(seen.len(), KeyCode::ALL.len());
+    }
+
+    #[test]
+    fn test_unknown_key_code_is_preserved() {
+        let raw = bindings::keyboard_event_data {
+            keycode: 0xE0F0,
+            rawcode: 0xFF1B,
+            keychar: bindings::CHAR_UNDEFINED as u16,
+        };
+        assert_eq!(KeyCode::try_from(0xE0F0), Err(UiohookError::UnknownKeyCode(0xE0F0)));
+
+        let event = KeyboardEvent::from(&raw);
+        assert_eq!(event.key_code, KeyCode::Other(0xE0F0));
+        assert_eq!(bindings::keyboard_event_data::from(&event).keycode, 0xE0F0);
+    }
+
+    #[test]
+    fn test_type_text() {
+        use crate::hook::backend::MockBackend;
+        use KeyboardEventType::{Pressed, Released, Typed};
+
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        type_text(&hook, "Hi!\nü😀").unwrap();
+
+        let posted: Vec<_> = backend
+            .posted_events()
+            .into_iter()
+            .map(|event| match event {
+                UiohookEvent::Keyboard(ke) => (ke.event_type, ke.key_code, ke.key_char),
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            posted,
+            vec![
+                (Pressed, KeyCode::ShiftL, None),
+                (Pressed, KeyCode::H, None),
+                (Released, KeyCode::H, None),
+                (Released, KeyCode::ShiftL, None),
+                (Pressed, KeyCode::I, None),
+                (Released, KeyCode::I, None),
+                (Pressed, KeyCode::ShiftL, None),
+                (Pressed, KeyCode::Num1, None),
+                (Released, KeyCode::Num1, None),
+                (Released, KeyCode::ShiftL, None),
+                (Pressed, KeyCode::Enter, None),
+                (Released, KeyCode::Enter, None),
+                (Typed, KeyCode::Undefined, Some('ü')),
+                (Typed, KeyCode::Undefined, Some('😀')),
+            ]
+        );
+
+        // The emoji is posted to libuiohook as a surrogate pair.
+        let units: Vec<_> = backend.posted_events()[13]
+            .to_raw_events()
+            .iter()
+            .map(|raw| unsafe { raw.data.keyboard.keychar })
+            .collect();
+        assert_eq!(units, vec![0xD83D, 0xDE00]);
+    }
+
+    #[test]
+    fn test_type_text_with_layout() {
+        use crate::hook::backend::MockBackend;
+        use KeyboardEventType::{Pressed, Released};
+
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        let layout = |c: char| match c {
+            'z' => Some((KeyCode::Y, &[][..])),
+            '@' => Some((KeyCode::Q, &[KeyCode::AltR][..])),
+            _ => UsLayout.key_for(c),
+        };
+        type_text_with_layout(&hook, "z@\r\n\r", &layout, Duration::ZERO).unwrap();
+
+        let posted: Vec<_> = backend
+            .posted_events()
+            .into_iter()
+            .map(|event| match event {
+                UiohookEvent::Keyboard(ke) => (ke.event_type, ke.key_code),
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            posted,
+            vec![
+                (Pressed, KeyCode::Y),
+                (Released, KeyCode::Y),
+                (Pressed, KeyCode::AltR),
+                (Pressed, KeyCode::Q),
+                (Released, KeyCode::Q),
+                (Released, KeyCode::AltR),
+                // "\r\n" is one line break.
+                (Pressed, KeyCode::Enter),
+                (Released, KeyCode::Enter),
+                (Pressed, KeyCode::Enter),
+                (Released, KeyCode::Enter),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_us_layout_covers_printable_ascii() {
+        for c in (' '..='~').chain(['\t', '\n', '\r']) {
+            assert!(us_layout_key(c).is_some(), "{:?} is not on the layout", c);
+        }
+        assert_eq!(us_layout_key('?'), Some((KeyCode::Slash, true)));
+        assert_eq!(us_layout_key('é'), None);
+        assert_eq!(UsLayout.key_for('"'), Some((KeyCode::Quote, &[KeyCode::ShiftL][..])));
+    }
+
+    #[test]
+    fn test_key_names_round_trip() {
+        let mut names = HashSet::new();
+        for key in KeyCode::all() {
+            assert!(names.insert(normalize_key_name(key.name())), "{:?} has a duplicate name", key);
+            assert_eq!(key.name().parse::<KeyCode>(), Ok(key));
+            assert_eq!(key.to_string().parse::<KeyCode>(), Ok(key));
+            assert_eq!(format!("{:?}", key).parse::<KeyCode>(), Ok(key));
+        }
+        for &(alias, _) in KEY_ALIASES {
+            assert!(!names.contains(alias), "alias {:?} shadows a key name", alias);
+        }
+
+        assert_eq!(KeyCode::ShiftL.to_string(), "Left Shift");
+        assert_eq!(format!("{:<6}|", KeyCode::A), "A     |");
+        assert_eq!(KeyCode::Other(0xE0F0).to_string(), "0xE0F0");
+        assert_eq!("0xE0F0".parse::<KeyCode>(), Ok(KeyCode::Other(0xE0F0)));
+        assert_eq!("0x001E".parse::<KeyCode>(), Ok(KeyCode::A));
+    }
+
+    #[test]
+    fn test_key_name_aliases() {
+        let cases = [
+            ("ctrl", KeyCode::ControlL),
+            ("ESC", KeyCode::Escape),
+            ("pgup", KeyCode::PageUp),
+            ("page-down", KeyCode::PageDown),
+            ("kp_5", KeyCode::Kp5),
+            ("numpad 5", KeyCode::Kp5),
+            ("Keypad Page Up", KeyCode::KpPageUp),
+            ("caps lock", KeyCode::CapsLock),
+            ("1", KeyCode::Num1),
+            ("-", KeyCode::Minus),
+            ("cmd", KeyCode::MetaL),
+            (" f12 ", KeyCode::F12),
+        ];
+        for (name, key) in cases {
+            assert_eq!(name.parse::<KeyCode>(), Ok(key), "{:?}", name);
+        }
+        assert_eq!("hyper".parse::<KeyCode>(), Err(UiohookError::InvalidArgument("unknown key name")));
+        assert!("0xZZ".parse::<KeyCode>().is_err());
+    }
+
+    #[test]
+    fn test_key_categories() {
+        let count = |f: fn(&KeyCode) -> bool| KeyCode::all().filter(f).count();
+        assert_eq!(count(KeyCode::is_modifier), 8);
+        assert_eq!(count(KeyCode::is_function), 24);
+        assert_eq!(count(KeyCode::is_keypad), 30);
+        assert_eq!(count(KeyCode::is_media), 9);
+        assert!(KeyCode::ControlR.is_modifier() && !KeyCode::CapsLock.is_modifier());
+        assert!(KeyCode::KpEnter.is_keypad() && !KeyCode::Enter.is_keypad());
+        assert!(!KeyCode::Other(0xE0F0).is_media());
+    }
+
+    #[test]
+    fn test_keyboard_event_data_round_trip() {
+        for key in KeyCode::all() {
+            for keychar in [bindings::CHAR_UNDEFINED as u16, 'a' as u16, 'é' as u16, '€' as u16] {
+                let raw = bindings::keyboard_event_data {
+                    keycode: u32::from(key) as u16,
+                    rawcode: 0x1234,
+                    keychar,
+                };
+                let event = KeyboardEvent::from(&raw);
+                let back = bindings::keyboard_event_data::from(&event);
+                assert_eq!(event.key_code, key);
+                assert_eq!(back.keycode, raw.keycode, "{:?}", key);
+                assert_eq!(back.rawcode, raw.rawcode);
+                assert_eq!(back.keychar, raw.keychar);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_char_encoding() {
+        let undefined = bindings::CHAR_UNDEFINED as u16;
+        let mut event = create_keyboard_event(KeyboardEventType::Typed, KeyCode::A);
+        assert_eq!(bindings::keyboard_event_data::from(&event).keychar, undefined);
+
+        event.key_char = Some('A');
+        assert_eq!(bindings::keyboard_event_data::from(&event).keychar, 'A' as u16);
+
+        // Not representable in a single UTF-16 unit.
+        event.key_char = Some('😀');
+        assert_eq!(bindings::keyboard_event_data::from(&event).keychar, undefined);
+
+        let raw = bindings::keyboard_event_data {
+            keycode: bindings::VC_A as u16,
+            rawcode: 0,
+            keychar: undefined,
+        };
+        assert_eq!(KeyboardEvent::from(&raw).key_char, None);
+    }
+
     // Add more tests as needed
 }
\ No newline at end of file
@@ -0,0 +1,299 @@
+//! Typed access to the modifier mask carried by every input event.
+
+use crate::bindings;
+use bitflags::bitflags;
+use std::fmt;
+
+bitflags! {
+    /// Modifier keys, held mouse buttons and lock states active during an event.
+    ///
+    /// This wraps libuiohook's `MASK_*` bits. Unknown bits are preserved, so
+    /// converting to and from the raw `u16` mask is lossless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::modifiers::Modifiers;
+    ///
+    /// let modifiers = Modifiers::CTRL_L | Modifiers::SHIFT_R;
+    /// assert!(modifiers.ctrl());
+    /// assert!(modifiers.shift_right());
+    /// assert!(!modifiers.shift_left());
+    /// assert_eq!(modifiers.to_string(), "Ctrl+Shift");
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct Modifiers: u16 {
+        /// Left Shift key.
+        const SHIFT_L = bindings::MASK_SHIFT_L as u16;
+        /// Left Control key.
+        const CTRL_L = bindings::MASK_CTRL_L as u16;
+        /// Left Meta (Command / Windows) key.
+        const META_L = bindings::MASK_META_L as u16;
+        /// Left Alt (Option) key.
+        const ALT_L = bindings::MASK_ALT_L as u16;
+        /// Right Shift key.
+        const SHIFT_R = bindings::MASK_SHIFT_R as u16;
+        /// Right Control key.
+        const CTRL_R = bindings::MASK_CTRL_R as u16;
+        /// Right Meta (Command / Windows) key.
+        const META_R = bindings::MASK_META_R as u16;
+        /// Right Alt (Option) key.
+        const ALT_R = bindings::MASK_ALT_R as u16;
+        /// Either Shift key.
+        const SHIFT = bindings::MASK_SHIFT as u16;
+        /// Either Control key.
+        const CTRL = bindings::MASK_CTRL as u16;
+        /// Either Meta key.
+        const META = bindings::MASK_META as u16;
+        /// Either Alt key.
+        const ALT = bindings::MASK_ALT as u16;
+        /// Mouse button 1 (usually left) is held.
+        const BUTTON1 = bindings::MASK_BUTTON1 as u16;
+        /// Mouse button 2 (usually right) is held.
+        const BUTTON2 = bindings::MASK_BUTTON2 as u16;
+        /// Mouse button 3 (usually middle) is held.
+        const BUTTON3 = bindings::MASK_BUTTON3 as u16;
+        /// Mouse button 4 is held.
+        const BUTTON4 = bindings::MASK_BUTTON4 as u16;
+        /// Mouse button 5 is held.
+        const BUTTON5 = bindings::MASK_BUTTON5 as u16;
+        /// Num Lock is on.
+        const NUM_LOCK = bindings::MASK_NUM_LOCK as u16;
+        /// Caps Lock is on.
+        const CAPS_LOCK = bindings::MASK_CAPS_LOCK as u16;
+        /// Scroll Lock is on.
+        const SCROLL_LOCK = bindings::MASK_SCROLL_LOCK as u16;
+
+        // Keep bits libuiohook may add in the future.
+        const _ = !0;
+    }
+}
+
+impl Modifiers {
+    /// All modifier key bits, without mouse buttons or lock states.
+    pub const KEYS: Modifiers = Modifiers::SHIFT
+        .union(Modifiers::CTRL)
+        .union(Modifiers::META)
+        .union(Modifiers::ALT);
+
+    /// All mouse button bits.
+    pub const BUTTONS: Modifiers = Modifiers::BUTTON1
+        .union(Modifiers::BUTTON2)
+        .union(Modifiers::BUTTON3)
+        .union(Modifiers::BUTTON4)
+        .union(Modifiers::BUTTON5);
+
+    /// All lock state bits.
+    pub const LOCKS: Modifiers = Modifiers::NUM_LOCK
+        .union(Modifiers::CAPS_LOCK)
+        .union(Modifiers::SCROLL_LOCK);
+
+    /// Returns `true` if either Shift key is held.
+    pub fn shift(&self) -> bool {
+        self.intersects(Modifiers::SHIFT)
+    }
+
+    /// Returns `true` if either Control key is held.
+    pub fn ctrl(&self) -> bool {
+        self.intersects(Modifiers::CTRL)
+    }
+
+    /// Returns `true` if either Meta key is held.
+    pub fn meta(&self) -> bool {
+        self.intersects(Modifiers::META)
+    }
+
+    /// Returns `true` if either Alt key is held.
+    pub fn alt(&self) -> bool {
+        self.intersects(Modifiers::ALT)
+    }
+
+    /// Returns `true` if either Shift key is held. Same as [`Modifiers::shift`].
+    pub fn any_shift(&self) -> bool {
+        self.shift()
+    }
+
+    /// Returns `true` if either Control key is held. Same as [`Modifiers::ctrl`].
+    pub fn any_ctrl(&self) -> bool {
+        self.ctrl()
+    }
+
+    /// Returns `true` if either Meta key is held. Same as [`Modifiers::meta`].
+    pub fn any_meta(&self) -> bool {
+        self.meta()
+    }
+
+    /// Returns `true` if either Alt key is held. Same as [`Modifiers::alt`].
+    pub fn any_alt(&self) -> bool {
+        self.alt()
+    }
+
+    /// Returns `true` if the left Shift key is held.
+    pub fn shift_left(&self) -> bool {
+        self.contains(Modifiers::SHIFT_L)
+    }
+
+    /// Returns `true` if the right Shift key is held.
+    pub fn shift_right(&self) -> bool {
+        self.contains(Modifiers::SHIFT_R)
+    }
+
+    /// Returns `true` if the left Control key is held.
+    pub fn ctrl_left(&self) -> bool {
+        self.contains(Modifiers::CTRL_L)
+    }
+
+    /// Returns `true` if the right Control key is held.
+    pub fn ctrl_right(&self) -> bool {
+        self.contains(Modifiers::CTRL_R)
+    }
+
+    /// Returns `true` if the left Meta key is held.
+    pub fn meta_left(&self) -> bool {
+        self.contains(Modifiers::META_L)
+    }
+
+    /// Returns `true` if the right Meta key is held.
+    pub fn meta_right(&self) -> bool {
+        self.contains(Modifiers::META_R)
+    }
+
+    /// Returns `true` if the left Alt key is held.
+    pub fn alt_left(&self) -> bool {
+        self.contains(Modifiers::ALT_L)
+    }
+
+    /// Returns `true` if the right Alt key is held.
+    pub fn alt_right(&self) -> bool {
+        self.contains(Modifiers::ALT_R)
+    }
+
+    /// Returns `true` if any modifier key is held.
+    pub fn any_key(&self) -> bool {
+        self.intersects(Modifiers::KEYS)
+    }
+
+    /// Returns `true` if mouse button `n` (1 to 5) is held.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The libuiohook button number, as in `MOUSE_BUTTON1..5`.
+    ///
+    /// # Returns
+    ///
+    /// `false` for button numbers outside `1..=5`.
+    pub fn button(&self, n: u8) -> bool {
+        match n {
+            1 => self.contains(Modifiers::BUTTON1),
+            2 => self.contains(Modifiers::BUTTON2),
+            3 => self.contains(Modifiers::BUTTON3),
+            4 => self.contains(Modifiers::BUTTON4),
+            5 => self.contains(Modifiers::BUTTON5),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if any mouse button is held.
+    pub fn any_button(&self) -> bool {
+        self.intersects(Modifiers::BUTTONS)
+    }
+
+    /// Returns `true` if Caps Lock is on.
+    pub fn caps_lock(&self) -> bool {
+        self.contains(Modifiers::CAPS_LOCK)
+    }
+
+    /// Returns `true` if Num Lock is on.
+    pub fn num_lock(&self) -> bool {
+        self.contains(Modifiers::NUM_LOCK)
+    }
+
+    /// Returns `true` if Scroll Lock is on.
+    pub fn scroll_lock(&self) -> bool {
+        self.contains(Modifiers::SCROLL_LOCK)
+    }
+}
+
+impl From<u16> for Modifiers {
+    fn from(mask: u16) -> Self {
+        Modifiers::from_bits_retain(mask)
+    }
+}
+
+impl From<Modifiers> for u16 {
+    fn from(modifiers: Modifiers) -> Self {
+        modifiers.bits()
+    }
+}
+
+/// Formats the held modifier keys as `Ctrl+Alt+Shift+Meta`, ignoring sides,
+/// mouse buttons and lock states. No modifiers format as an empty string.
+impl fmt::Display for Modifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (self.ctrl(), "Ctrl"),
+            (self.alt(), "Alt"),
+            (self.shift(), "Shift"),
+            (self.meta(), "Meta"),
+        ];
+
+        let mut first = true;
+        for (_, name) in names.iter().filter(|(held, _)| *held) {
+            if !first {
+                f.write_str("+")?;
+            }
+            f.write_str(name)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_aware_queries() {
+        let modifiers = Modifiers::CTRL_R | Modifiers::ALT_L;
+        assert!(modifiers.ctrl());
+        assert!(modifiers.ctrl_right());
+        assert!(!modifiers.ctrl_left());
+        assert!(modifiers.any_alt());
+        assert!(modifiers.alt_left());
+        assert!(!modifiers.shift());
+        assert!(!modifiers.meta());
+    }
+
+    #[test]
+    fn test_buttons_and_locks() {
+        let modifiers = Modifiers::BUTTON1 | Modifiers::BUTTON3 | Modifiers::CAPS_LOCK;
+        assert!(modifiers.button(1));
+        assert!(!modifiers.button(2));
+        assert!(modifiers.button(3));
+        assert!(!modifiers.button(6));
+        assert!(modifiers.any_button());
+        assert!(modifiers.caps_lock());
+        assert!(!modifiers.num_lock());
+        assert!(!modifiers.scroll_lock());
+        assert!(!modifiers.any_key());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Modifiers::empty().to_string(), "");
+        assert_eq!((Modifiers::SHIFT_L | Modifiers::CTRL_R).to_string(), "Ctrl+Shift");
+        assert_eq!(
+            (Modifiers::META_L | Modifiers::ALT_R | Modifiers::NUM_LOCK).to_string(),
+            "Alt+Meta"
+        );
+    }
+
+    #[test]
+    fn test_raw_mask_round_trip() {
+        for mask in [0u16, 0x0011, 0x8100, 0xFFFF] {
+            assert_eq!(u16::from(Modifiers::from(mask)), mask);
+        }
+        assert_eq!(Modifiers::from(bindings::MASK_CTRL as u16), Modifiers::CTRL);
+    }
+}
@@ -1,7 +1,10 @@
-use crate::bindings;
+use crate::{bindings, EventMeta, Modifiers};
 use crate::error::UiohookError;
+use crate::utils::DEFAULT_MULTI_CLICK_TIME;
 use crate::Uiohook;
 use std::convert::TryFrom;
+use std::thread;
+use std::time::Duration;
 
 /// Represents the type of mouse event.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,7 +24,7 @@ pub enum MouseEventType {
 /// Represents a mouse button.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MouseButton {
-    /// No button or an unknown button.
+    /// No button.
     NoButton,
     /// Left mouse button.
     Button1,
@@ -33,6 +36,8 @@ pub enum MouseButton {
     Button4,
     /// Additional mouse button 2.
     Button5,
+    /// A button beyond the five libuiohook names, holding its raw button number.
+    Other(u16),
 }
 
 impl TryFrom<u32> for MouseButton {
@@ -60,6 +65,7 @@ impl From<MouseButton> for u32 {
             MouseButton::Button3 => bindings::MOUSE_BUTTON3,
             MouseButton::Button4 => bindings::MOUSE_BUTTON4,
             MouseButton::Button5 => bindings::MOUSE_BUTTON5,
+            MouseButton::Other(button) => button as u32,
         }
     }
 }
@@ -77,13 +83,27 @@ pub struct MouseEvent {
     pub x: i16,
     /// The y-coordinate of the mouse cursor.
     pub y: i16,
+    /// The timestamp and modifier state of the event.
+    pub meta: EventMeta,
 }
 
 impl From<&bindings::mouse_event_data> for MouseEvent {
     fn from(event: &bindings::mouse_event_data) -> Self {
         MouseEvent {
             event_type: MouseEventType::Moved, // This will be set correctly by the caller
-            button: MouseButton::try_from(event.button as u32).unwrap_or(MouseButton::NoButton),
+            button: MouseButton::try_from(event.button as u32).unwrap_or(MouseButton::Other(event.button)),
+            clicks: event.clicks,
+            x: event.x,
+            y: event.y,
+            meta: EventMeta::default(), // This will be set correctly by the caller
+        }
+    }
+}
+
+impl From<&MouseEvent> for bindings::mouse_event_data {
+    fn from(event: &MouseEvent) -> Self {
+        bindings::mouse_event_data {
+            button: u32::from(event.button) as u16,
             clicks: event.clicks,
             x: event.x,
             y: event.y,
@@ -107,7 +127,7 @@ impl From<&bindings::mouse_event_data> for MouseEvent {
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, mouse::{mouse_press, MouseButton}};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_press, MouseButton}};
 ///
 /// struct MyHandler;
 ///
@@ -141,7 +161,7 @@ pub fn mouse_press(uiohook: &Uiohook, button: MouseButton, x: i16, y: i16) -> Re
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, mouse::{mouse_release, MouseButton}};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_release, MouseButton}};
 ///
 /// struct MyHandler;
 ///
@@ -175,7 +195,7 @@ pub fn mouse_release(uiohook: &Uiohook, button: MouseButton, x: i16, y: i16) ->
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, mouse::{mouse_click, MouseButton}};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_click, MouseButton}};
 ///
 /// struct MyHandler;
 ///
@@ -208,7 +228,7 @@ pub fn mouse_click(uiohook: &Uiohook, button: MouseButton, x: i16, y: i16) -> Re
 /// # Examples
 ///
 /// ```no_run
-/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, mouse::mouse_move};
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::mouse_move};
 ///
 /// struct MyHandler;
 ///
@@ -226,6 +246,185 @@ pub fn mouse_move(uiohook: &Uiohook, x: i16, y: i16) -> Result<(), UiohookError>
     uiohook.post_event(&crate::UiohookEvent::Mouse(event))
 }
 
+/// Simulates a double click.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `button` - The mouse button to click.
+/// * `x` - The x-coordinate for the mouse event.
+/// * `y` - The y-coordinate for the mouse event.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_double_click, MouseButton}};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// mouse_double_click(&hook, MouseButton::Button1, 100, 100).expect("Failed to double click");
+/// ```
+pub fn mouse_double_click(uiohook: &Uiohook, button: MouseButton, x: i16, y: i16) -> Result<(), UiohookError> {
+    mouse_multi_click(uiohook, button, x, y, 2)
+}
+
+/// Simulates `clicks` consecutive clicks that together form a multi-click.
+///
+/// Each press and release carries its position in the sequence in `clicks`,
+/// and the clicks are spaced well within the system's multi-click time so they
+/// are not seen as separate clicks. If the system does not report a
+/// multi-click time, [`DEFAULT_MULTI_CLICK_TIME`] is assumed.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `button` - The mouse button to click.
+/// * `x` - The x-coordinate for the mouse event.
+/// * `y` - The y-coordinate for the mouse event.
+/// * `clicks` - The number of clicks, e.g. 3 for a triple click.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Errors
+///
+/// Returns `UiohookError::InvalidArgument` if `clicks` is zero, or a
+/// `UiohookError` if an event fails to post.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_multi_click, MouseButton}};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// mouse_multi_click(&hook, MouseButton::Button1, 100, 100, 3).expect("Failed to triple click");
+/// ```
+pub fn mouse_multi_click(uiohook: &Uiohook, button: MouseButton, x: i16, y: i16, clicks: u16) -> Result<(), UiohookError> {
+    if clicks == 0 {
+        return Err(UiohookError::InvalidArgument("clicks must not be zero"));
+    }
+    let multi_click_time = uiohook.multi_click_time().unwrap_or(DEFAULT_MULTI_CLICK_TIME).max(0) as u64;
+    let gap = Duration::from_millis(multi_click_time / 10);
+
+    for click in 1..=clicks {
+        if click > 1 {
+            thread::sleep(gap);
+        }
+        let press = create_mouse_event(MouseEventType::Pressed, button, click, x, y);
+        uiohook.post_event(&crate::UiohookEvent::Mouse(press))?;
+        let release = create_mouse_event(MouseEventType::Released, button, click, x, y);
+        uiohook.post_event(&crate::UiohookEvent::Mouse(release))?;
+    }
+    Ok(())
+}
+
+/// Simulates dragging with a button held down, for drag-and-drop.
+///
+/// Moves to `from`, presses `button`, drags to `to` in `steps` evenly spaced
+/// `Dragged` events spread over `duration`, and releases `button` at `to`.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `button` - The mouse button to hold during the drag.
+/// * `from` - The (x, y) position where the drag starts.
+/// * `to` - The (x, y) position where the drag ends.
+/// * `steps` - The number of `Dragged` events between `from` and `to`.
+/// * `duration` - The time taken to drag from `from` to `to`.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Errors
+///
+/// Returns `UiohookError::InvalidArgument` if `steps` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent, hook::mouse::{mouse_drag, MouseButton}};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// mouse_drag(&hook, MouseButton::Button1, (100, 100), (400, 250), 20, Duration::from_millis(200))
+///     .expect("Failed to drag");
+/// ```
+pub fn mouse_drag(
+    uiohook: &Uiohook,
+    button: MouseButton,
+    from: (i16, i16),
+    to: (i16, i16),
+    steps: u32,
+    duration: Duration,
+) -> Result<(), UiohookError> {
+    if steps == 0 {
+        return Err(UiohookError::InvalidArgument("steps must not be zero"));
+    }
+    let interval = duration / steps;
+
+    mouse_move(uiohook, from.0, from.1)?;
+    mouse_press(uiohook, button, from.0, from.1)?;
+
+    for step in 1..=steps {
+        thread::sleep(interval);
+        let x = interpolate(from.0, to.0, step, steps);
+        let y = interpolate(from.1, to.1, step, steps);
+        // libuiohook reports the held button in the mask, not in `button`.
+        let mut event = create_mouse_event(MouseEventType::Dragged, MouseButton::NoButton, 0, x, y);
+        event.meta.modifiers = button_mask(button);
+        uiohook.post_event(&crate::UiohookEvent::Mouse(event))?;
+    }
+
+    mouse_release(uiohook, button, to.0, to.1)
+}
+
+// Position `step` of `steps` along the line from `from` to `to`.
+fn interpolate(from: i16, to: i16, step: u32, steps: u32) -> i16 {
+    let offset = (i64::from(to) - i64::from(from)) * i64::from(step) / i64::from(steps);
+    (i64::from(from) + offset) as i16
+}
+
+// The modifier bit libuiohook sets while `button` is held.
+fn button_mask(button: MouseButton) -> Modifiers {
+    match button {
+        MouseButton::Button1 => Modifiers::BUTTON1,
+        MouseButton::Button2 => Modifiers::BUTTON2,
+        MouseButton::Button3 => Modifiers::BUTTON3,
+        MouseButton::Button4 => Modifiers::BUTTON4,
+        MouseButton::Button5 => Modifiers::BUTTON5,
+        MouseButton::NoButton | MouseButton::Other(_) => Modifiers::empty(),
+    }
+}
+
 // Helper function to create a MouseEvent
 fn create_mouse_event(event_type: MouseEventType, button: MouseButton, clicks: u16, x: i16, y: i16) -> MouseEvent {
     MouseEvent {
@@ -234,12 +433,27 @@ fn create_mouse_event(event_type: MouseEventType, button: MouseButton, clicks: u
         clicks,
         x,
         y,
+        meta: EventMeta::default(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hook::backend::MockBackend;
+    use crate::{SystemProperties, UiohookEvent};
+    use std::time::Instant;
+
+    fn posted_mouse_events(backend: &MockBackend) -> Vec<MouseEvent> {
+        backend
+            .take_posted_events()
+            .into_iter()
+            .map(|event| match event {
+                UiohookEvent::Mouse(me) => me,
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect()
+    }
 
     #[test]
     fn test_mouse_button_conversion() {
@@ -267,5 +481,127 @@ mod tests {
         assert_eq!(event.y, 200);
     }
 
+    #[test]
+    fn test_unknown_mouse_button_is_preserved() {
+        let raw = bindings::mouse_event_data {
+            button: 8,
+            clicks: 1,
+            x: 0,
+            y: 0,
+        };
+        let event = MouseEvent::from(&raw);
+        assert_eq!(event.button, MouseButton::Other(8));
+        assert_eq!(bindings::mouse_event_data::from(&event).button, 8);
+    }
+
+    #[test]
+    fn test_multi_click() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        backend.set_system_properties(SystemProperties {
+            multi_click_time: Some(200),
+            ..SystemProperties::default()
+        });
+
+        let start = Instant::now();
+        mouse_multi_click(&hook, MouseButton::Button1, 5, 6, 3).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let posted = posted_mouse_events(&backend);
+        let sequence: Vec<_> = posted.iter().map(|me| (me.event_type, me.clicks)).collect();
+        assert_eq!(
+            sequence,
+            vec![
+                (MouseEventType::Pressed, 1),
+                (MouseEventType::Released, 1),
+                (MouseEventType::Pressed, 2),
+                (MouseEventType::Released, 2),
+                (MouseEventType::Pressed, 3),
+                (MouseEventType::Released, 3),
+            ]
+        );
+        assert!(posted.iter().all(|me| me.button == MouseButton::Button1 && (me.x, me.y) == (5, 6)));
+
+        mouse_double_click(&hook, MouseButton::Button2, 0, 0).unwrap();
+        assert_eq!(posted_mouse_events(&backend).last().map(|me| me.clicks), Some(2));
+        assert_eq!(
+            mouse_multi_click(&hook, MouseButton::Button1, 0, 0, 0),
+            Err(UiohookError::InvalidArgument("clicks must not be zero"))
+        );
+    }
+
+    #[test]
+    fn test_multi_click_without_multi_click_time() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        backend.set_system_properties(SystemProperties::default());
+
+        let start = Instant::now();
+        mouse_double_click(&hook, MouseButton::Button1, 0, 0).unwrap();
+        let gap = Duration::from_millis(DEFAULT_MULTI_CLICK_TIME as u64 / 10);
+        assert!(start.elapsed() >= gap);
+        assert_eq!(posted_mouse_events(&backend).len(), 4);
+    }
+
+    #[test]
+    fn test_drag() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        mouse_drag(&hook, MouseButton::Button1, (10, 20), (-30, 100), 4, Duration::ZERO).unwrap();
+
+        let posted = posted_mouse_events(&backend);
+        let path: Vec<_> = posted.iter().map(|me| (me.event_type, me.x, me.y)).collect();
+        assert_eq!(
+            path,
+            vec![
+                (MouseEventType::Moved, 10, 20),
+                (MouseEventType::Pressed, 10, 20),
+                (MouseEventType::Dragged, 0, 40),
+                (MouseEventType::Dragged, -10, 60),
+                (MouseEventType::Dragged, -20, 80),
+                (MouseEventType::Dragged, -30, 100),
+                (MouseEventType::Released, -30, 100),
+            ]
+        );
+        assert_eq!(posted[1].button, MouseButton::Button1);
+        assert_eq!(posted[6].button, MouseButton::Button1);
+        assert!(posted[2..6].iter().all(|me| me.meta.modifiers.button(1)));
+
+        assert!(mouse_drag(&hook, MouseButton::Button1, (0, 0), (1, 1), 0, Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_every_mouse_button_round_trips() {
+        let buttons = [
+            MouseButton::NoButton,
+            MouseButton::Button1,
+            MouseButton::Button2,
+            MouseButton::Button3,
+            MouseButton::Button4,
+            MouseButton::Button5,
+        ];
+        for button in buttons {
+            let raw = bindings::mouse_event_data {
+                button: u32::from(button) as u16,
+                clicks: 2,
+                x: -5,
+                y: 7,
+            };
+            let event = MouseEvent::from(&raw);
+            assert_eq!(event.button, button);
+
+            let back = bindings::mouse_event_data::from(&event);
+            assert_eq!(back.button, raw.button, "{:?}", button);
+            assert_eq!((back.clicks, back.x, back.y), (raw.clicks, raw.x, raw.y));
+        }
+
+        for raw in 0..=u16::MAX as u32 {
+            if let Ok(button) = MouseButton::try_from(raw) {
+                assert_eq!(u32::from(button), raw);
+                assert!(buttons.contains(&button));
+            }
+        }
+    }
+
     // Add more tests as needed
 }
\ No newline at end of file
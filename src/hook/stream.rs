@@ -0,0 +1,146 @@
+//! Async event delivery (requires the `async` feature).
+//!
+//! [`EventStream`] is a [`Stream`] of captured events backed by the same bounded
+//! channel as [`EventReceiver`], so backpressure follows the same
+//! [`OverflowPolicy`](super::channel::OverflowPolicy). [`Uiohook::stop_async`](crate::Uiohook::stop_async) stops the
+//! hook without blocking the executor while the hook thread shuts down.
+
+use super::channel::EventReceiver;
+use super::UiohookEvent;
+use crate::error::UiohookError;
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// A stream of captured events created by
+/// [`Uiohook::with_stream`](crate::Uiohook::with_stream).
+///
+/// The stream ends once the owning `Uiohook` has been dropped and all queued
+/// events have been yielded.
+pub struct EventStream {
+    receiver: EventReceiver,
+}
+
+impl EventStream {
+    pub(crate) fn new(receiver: EventReceiver) -> Self {
+        Self { receiver }
+    }
+
+    /// Returns the number of events discarded because the stream's buffer was full.
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = UiohookEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.receiver.len(), None)
+    }
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("queued", &self.receiver.len())
+            .finish()
+    }
+}
+
+/// Resolves once a stopping hook thread has exited.
+///
+/// The join happens on a helper thread so awaiting it never blocks the executor.
+pub(crate) struct ThreadExit {
+    shared: Arc<Mutex<ExitState>>,
+}
+
+struct ExitState {
+    result: Option<Result<(), UiohookError>>,
+    waker: Option<Waker>,
+}
+
+// Waking the executor must not panic because an earlier holder of the lock did.
+fn lock(state: &Mutex<ExitState>) -> MutexGuard<'_, ExitState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl ThreadExit {
+    pub(crate) fn new(thread: Option<thread::JoinHandle<()>>) -> Self {
+        let shared = Arc::new(Mutex::new(ExitState {
+            result: None,
+            waker: None,
+        }));
+        match thread {
+            Some(thread) => {
+                let exit = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let result = thread.join().map_err(|_| UiohookError::Failure);
+                    let mut state = lock(&exit);
+                    state.result = Some(result);
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                });
+            }
+            None => lock(&shared).result = Some(Ok(())),
+        }
+        Self { shared }
+    }
+}
+
+impl Future for ThreadExit {
+    type Output = Result<(), UiohookError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.shared);
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::channel::{channel, OverflowPolicy};
+    use crate::hook::EventHandler;
+    use futures::executor::block_on;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stream_yields_events_until_disconnected() {
+        let (handler, receiver) = channel(8, OverflowPolicy::DropOldest);
+        let mut stream = EventStream::new(receiver);
+
+        let producer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            handler.handle_event(&UiohookEvent::HookEnabled);
+            handler.handle_event(&UiohookEvent::HookDisabled);
+        });
+
+        assert!(matches!(block_on(stream.next()), Some(UiohookEvent::HookEnabled)));
+        assert!(matches!(block_on(stream.next()), Some(UiohookEvent::HookDisabled)));
+        assert!(block_on(stream.next()).is_none());
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn test_thread_exit_resolves_after_join() {
+        let thread = thread::spawn(|| thread::sleep(Duration::from_millis(10)));
+        assert_eq!(block_on(ThreadExit::new(Some(thread))), Ok(()));
+        assert_eq!(block_on(ThreadExit::new(None)), Ok(()));
+    }
+}
@@ -1,9 +1,84 @@
-use crate::bindings;
+use crate::{bindings, EventMeta, UiohookEvent};
+use crate::error::{Result, UiohookError};
+use crate::Uiohook;
+use std::convert::TryFrom;
+use std::thread;
+use std::time::Duration;
 
 /// Constants for wheel scroll directions
 pub const WHEEL_VERTICAL_DIRECTION: u8 = bindings::WHEEL_VERTICAL_DIRECTION as u8;
 /// Constant for horizontal wheel scroll direction
 pub const WHEEL_HORIZONTAL_DIRECTION: u8 = bindings::WHEEL_HORIZONTAL_DIRECTION as u8;
+/// The rotation Windows reports for one wheel notch.
+pub const WHEEL_DELTA: i16 = 120;
+
+/// Represents how the amount of a wheel event is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WheelScrollType {
+    /// The amount is a number of lines (or pixels on platforms that scroll smoothly).
+    #[default]
+    Unit,
+    /// The amount is a number of pages.
+    Block,
+    /// A scroll type libuiohook does not name, holding its raw value.
+    Other(u8),
+}
+
+impl TryFrom<u8> for WheelScrollType {
+    type Error = UiohookError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value as u32 {
+            bindings::WHEEL_UNIT_SCROLL => Ok(WheelScrollType::Unit),
+            bindings::WHEEL_BLOCK_SCROLL => Ok(WheelScrollType::Block),
+            _ => Err(UiohookError::InvalidArgument("unknown wheel scroll type")),
+        }
+    }
+}
+
+impl From<WheelScrollType> for u8 {
+    fn from(scroll_type: WheelScrollType) -> Self {
+        match scroll_type {
+            WheelScrollType::Unit => bindings::WHEEL_UNIT_SCROLL as u8,
+            WheelScrollType::Block => bindings::WHEEL_BLOCK_SCROLL as u8,
+            WheelScrollType::Other(scroll_type) => scroll_type,
+        }
+    }
+}
+
+/// Represents the axis a wheel event scrolls along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WheelDirection {
+    /// Scrolling up or down.
+    #[default]
+    Vertical,
+    /// Scrolling left or right.
+    Horizontal,
+    /// A direction libuiohook does not name, holding its raw value.
+    Other(u8),
+}
+
+impl TryFrom<u8> for WheelDirection {
+    type Error = UiohookError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            WHEEL_VERTICAL_DIRECTION => Ok(WheelDirection::Vertical),
+            WHEEL_HORIZONTAL_DIRECTION => Ok(WheelDirection::Horizontal),
+            _ => Err(UiohookError::InvalidArgument("unknown wheel direction")),
+        }
+    }
+}
+
+impl From<WheelDirection> for u8 {
+    fn from(direction: WheelDirection) -> Self {
+        match direction {
+            WheelDirection::Vertical => WHEEL_VERTICAL_DIRECTION,
+            WheelDirection::Horizontal => WHEEL_HORIZONTAL_DIRECTION,
+            WheelDirection::Other(direction) => direction,
+        }
+    }
+}
 
 /// Represents a mouse wheel event.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,14 +89,18 @@ pub struct WheelEvent {
     pub x: i16,
     /// The y-coordinate of the mouse pointer.
     pub y: i16,
-    /// The type of mouse wheel event.
-    pub type_: u8,
-    /// The amount of scrolling.
+    /// How `amount` is measured.
+    pub scroll_type: WheelScrollType,
+    /// The amount scrolled per rotation unit.
     pub amount: u16,
     /// The number of rotation units the mouse wheel was scrolled.
+    ///
+    /// Negative values scroll up or left, positive values down or right.
     pub rotation: i16,
     /// The direction of the scroll (vertical or horizontal).
-    pub direction: u8,
+    pub direction: WheelDirection,
+    /// The timestamp and modifier state of the event.
+    pub meta: EventMeta,
 }
 
 impl From<&bindings::mouse_wheel_event_data> for WheelEvent {
@@ -30,58 +109,112 @@ impl From<&bindings::mouse_wheel_event_data> for WheelEvent {
             clicks: event.clicks,
             x: event.x,
             y: event.y,
-            type_: event.type_,
+            scroll_type: WheelScrollType::try_from(event.type_).unwrap_or(WheelScrollType::Other(event.type_)),
+            amount: event.amount,
+            rotation: event.rotation,
+            direction: WheelDirection::try_from(event.direction).unwrap_or(WheelDirection::Other(event.direction)),
+            meta: EventMeta::default(), // This will be set correctly by the caller
+        }
+    }
+}
+
+impl From<&WheelEvent> for bindings::mouse_wheel_event_data {
+    fn from(event: &WheelEvent) -> Self {
+        bindings::mouse_wheel_event_data {
+            clicks: event.clicks,
+            x: event.x,
+            y: event.y,
+            type_: event.scroll_type.into(),
             amount: event.amount,
             rotation: event.rotation,
-            direction: event.direction,
+            direction: event.direction.into(),
         }
     }
 }
 
 impl WheelEvent {
-    /// Creates a new `WheelEvent` instance.
+    /// Creates a builder for a `WheelEvent`.
+    ///
+    /// # Returns
+    ///
+    /// A `WheelEventBuilder` for a vertical unit scroll of 3 per rotation unit
+    /// at (0, 0); the rotation must be set before building.
     ///
-    /// # Arguments
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::wheel::{WheelDirection, WheelEvent};
+    ///
+    /// let wheel_event = WheelEvent::builder()
+    ///     .position(100, 200)
+    ///     .direction(WheelDirection::Vertical)
+    ///     .rotation(-1)
+    ///     .build()
+    ///     .expect("Invalid wheel event");
+    /// assert_eq!(wheel_event.raw_delta(), -3);
+    /// ```
+    pub fn builder() -> WheelEventBuilder {
+        WheelEventBuilder::new()
+    }
+
+    /// Returns `rotation` multiplied by `amount`, without normalizing either.
     ///
-    /// * `clicks` - The number of clicks.
-    /// * `x` - The x-coordinate of the mouse pointer.
-    /// * `y` - The y-coordinate of the mouse pointer.
-    /// * `type_` - The type of mouse wheel event.
-    /// * `amount` - The amount of scrolling.
-    /// * `rotation` - The number of rotation units the mouse wheel was scrolled.
-    /// * `direction` - The direction of the scroll (vertical or horizontal).
+    /// The result is in the units the platform reports. For events built with
+    /// [`WheelEvent::builder`] that is lines for unit scrolls and pages for
+    /// block scrolls, but captured events on Windows report `rotation` in
+    /// multiples of [`WHEEL_DELTA`] (120 per notch). Use [`WheelEvent::delta`]
+    /// for a value that is comparable across platforms.
     ///
     /// # Returns
     ///
-    /// A new `WheelEvent` instance.
+    /// The signed product; negative values scroll up or left, positive values
+    /// down or right.
+    pub fn raw_delta(&self) -> i32 {
+        i32::from(self.rotation) * i32::from(self.amount)
+    }
+
+    /// Returns the signed scroll distance, normalized across platforms.
+    ///
+    /// `rotation` is first converted to wheel notches: a multiple of
+    /// [`WHEEL_DELTA`] is taken to be in Windows units and divided by it, any
+    /// other value is already a notch count. Unit scrolls then multiply the
+    /// notches by `amount` to give lines (pixels on platforms that scroll
+    /// smoothly). Block scrolls return the notches as pages and ignore
+    /// `amount`, which does not hold a line count for them. Scroll types
+    /// libuiohook does not name are treated like unit scrolls.
+    ///
+    /// # Returns
+    ///
+    /// Lines for unit scrolls or pages for block scrolls; negative values
+    /// scroll up or left, positive values down or right.
     ///
     /// # Examples
     ///
     /// ```
-    /// use uiohook_rs::hook::wheel::WheelEvent;
-    /// use uiohook_rs::hook::wheel::WHEEL_VERTICAL_DIRECTION;
-    /// use uiohook_rs::bindings;
-    ///
-    /// let wheel_event = WheelEvent::new(
-    ///     1,
-    ///     100,
-    ///     200,
-    ///     bindings::WHEEL_UNIT_SCROLL as u8,
-    ///     3,
-    ///     -120,
-    ///     WHEEL_VERTICAL_DIRECTION,
-    /// );
-    /// assert_eq!(wheel_event.clicks, 1);
+    /// use uiohook_rs::hook::wheel::{WheelEvent, WheelScrollType};
+    ///
+    /// let notch = WheelEvent::builder().rotation(1).amount(3).build().unwrap();
+    /// let windows_notch = WheelEvent::builder().rotation(120).amount(3).build().unwrap();
+    /// assert_eq!(notch.delta(), 3);
+    /// assert_eq!(windows_notch.delta(), 3);
+    ///
+    /// let page_up = WheelEvent::builder()
+    ///     .scroll_type(WheelScrollType::Block)
+    ///     .rotation(-1)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(page_up.delta(), -1);
     /// ```
-    pub fn new(clicks: u16, x: i16, y: i16, type_: u8, amount: u16, rotation: i16, direction: u8) -> Self {
-        WheelEvent {
-            clicks,
-            x,
-            y,
-            type_,
-            amount,
-            rotation,
-            direction,
+    pub fn delta(&self) -> i32 {
+        let notches = if self.rotation % WHEEL_DELTA == 0 {
+            self.rotation / WHEEL_DELTA
+        } else {
+            self.rotation
+        };
+
+        match self.scroll_type {
+            WheelScrollType::Block => i32::from(notches),
+            WheelScrollType::Unit | WheelScrollType::Other(_) => i32::from(notches) * i32::from(self.amount),
         }
     }
 
@@ -91,7 +224,7 @@ impl WheelEvent {
     ///
     /// `true` if the event is a vertical scroll, `false` otherwise.
     pub fn is_vertical(&self) -> bool {
-        self.direction == WHEEL_VERTICAL_DIRECTION
+        self.direction == WheelDirection::Vertical
     }
 
     /// Checks if the wheel event is a horizontal scroll.
@@ -100,13 +233,271 @@ impl WheelEvent {
     ///
     /// `true` if the event is a horizontal scroll, `false` otherwise.
     pub fn is_horizontal(&self) -> bool {
-        self.direction == WHEEL_HORIZONTAL_DIRECTION
+        self.direction == WheelDirection::Horizontal
     }
 }
 
+/// Builds a validated [`WheelEvent`].
+#[derive(Debug, Clone, Copy)]
+pub struct WheelEventBuilder {
+    event: WheelEvent,
+}
+
+impl WheelEventBuilder {
+    fn new() -> Self {
+        WheelEventBuilder {
+            event: WheelEvent {
+                clicks: 1,
+                x: 0,
+                y: 0,
+                scroll_type: WheelScrollType::Unit,
+                amount: 3,
+                rotation: 0,
+                direction: WheelDirection::Vertical,
+                meta: EventMeta::default(),
+            },
+        }
+    }
+
+    /// Set the number of clicks. Defaults to 1.
+    pub fn clicks(mut self, clicks: u16) -> Self {
+        self.event.clicks = clicks;
+        self
+    }
+
+    /// Set the position of the mouse pointer. Defaults to (0, 0).
+    pub fn position(mut self, x: i16, y: i16) -> Self {
+        self.event.x = x;
+        self.event.y = y;
+        self
+    }
+
+    /// Set how `amount` is measured. Defaults to [`WheelScrollType::Unit`].
+    pub fn scroll_type(mut self, scroll_type: WheelScrollType) -> Self {
+        self.event.scroll_type = scroll_type;
+        self
+    }
+
+    /// Set the amount scrolled per rotation unit. Defaults to 3.
+    pub fn amount(mut self, amount: u16) -> Self {
+        self.event.amount = amount;
+        self
+    }
+
+    /// Set the number of rotation units; negative values scroll up or left.
+    pub fn rotation(mut self, rotation: i16) -> Self {
+        self.event.rotation = rotation;
+        self
+    }
+
+    /// Set the direction of the scroll. Defaults to [`WheelDirection::Vertical`].
+    pub fn direction(mut self, direction: WheelDirection) -> Self {
+        self.event.direction = direction;
+        self
+    }
+
+    /// Set the timestamp and modifier state of the event.
+    pub fn meta(mut self, meta: EventMeta) -> Self {
+        self.event.meta = meta;
+        self
+    }
+
+    /// Build the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if the rotation or the amount is
+    /// zero, since such an event would not scroll.
+    pub fn build(self) -> Result<WheelEvent> {
+        if self.event.rotation == 0 {
+            return Err(UiohookError::InvalidArgument("wheel rotation must not be zero"));
+        }
+        if self.event.amount == 0 {
+            return Err(UiohookError::InvalidArgument("wheel amount must not be zero"));
+        }
+        Ok(self.event)
+    }
+}
+
+/// Simulates scrolling the mouse wheel.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `direction` - The axis to scroll along.
+/// * `amount` - The number of wheel notches to scroll; negative values scroll up or left.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Errors
+///
+/// Returns `UiohookError::InvalidArgument` if `amount` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+/// use uiohook_rs::hook::wheel::{wheel_scroll, WheelDirection};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// wheel_scroll(&hook, WheelDirection::Vertical, 3).expect("Failed to scroll");
+/// ```
+pub fn wheel_scroll(uiohook: &Uiohook, direction: WheelDirection, amount: i16) -> Result<()> {
+    let event = WheelEvent::builder().direction(direction).rotation(amount).build()?;
+    uiohook.post_event(&UiohookEvent::Wheel(event))
+}
+
+/// Simulates scrolling the mouse wheel up or down.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `amount` - The number of wheel notches to scroll; negative values scroll up.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+/// use uiohook_rs::hook::wheel::scroll_vertical;
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// scroll_vertical(&hook, -1).expect("Failed to scroll up");
+/// ```
+pub fn scroll_vertical(uiohook: &Uiohook, amount: i16) -> Result<()> {
+    wheel_scroll(uiohook, WheelDirection::Vertical, amount)
+}
+
+/// Simulates scrolling the mouse wheel left or right.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `amount` - The number of wheel notches to scroll; negative values scroll left.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+/// use uiohook_rs::hook::wheel::scroll_horizontal;
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// scroll_horizontal(&hook, 2).expect("Failed to scroll right");
+/// ```
+pub fn scroll_horizontal(uiohook: &Uiohook, amount: i16) -> Result<()> {
+    wheel_scroll(uiohook, WheelDirection::Horizontal, amount)
+}
+
+/// Simulates scrolling the mouse wheel one notch at a time, spread evenly over `duration`.
+///
+/// Blocks the calling thread until the last notch has been posted.
+///
+/// # Arguments
+///
+/// * `uiohook` - A reference to the Uiohook instance.
+/// * `direction` - The axis to scroll along.
+/// * `amount` - The number of wheel notches to scroll; negative values scroll up or left.
+/// * `duration` - The time between the first and the last notch.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the operation failed.
+///
+/// # Errors
+///
+/// Returns `UiohookError::InvalidArgument` if `amount` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use uiohook_rs::{Uiohook, EventHandler, UiohookEvent};
+/// use uiohook_rs::hook::wheel::{smooth_scroll, WheelDirection};
+///
+/// struct MyHandler;
+///
+/// impl EventHandler for MyHandler {
+///     fn handle_event(&self, event: &UiohookEvent) {
+///         println!("Event: {:?}", event);
+///     }
+/// }
+///
+/// let hook = Uiohook::new(MyHandler);
+/// smooth_scroll(&hook, WheelDirection::Vertical, 10, Duration::from_millis(250))
+///     .expect("Failed to scroll");
+/// ```
+pub fn smooth_scroll(uiohook: &Uiohook, direction: WheelDirection, amount: i16, duration: Duration) -> Result<()> {
+    let step = WheelEvent::builder().direction(direction).rotation(amount.signum()).build()?;
+    let steps = u32::from(amount.unsigned_abs());
+    let interval = if steps > 1 { duration / (steps - 1) } else { Duration::ZERO };
+
+    for i in 0..steps {
+        if i > 0 {
+            thread::sleep(interval);
+        }
+        uiohook.post_event(&UiohookEvent::Wheel(step))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hook::backend::MockBackend;
+    use std::time::Instant;
+
+    fn posted_wheel_events(backend: &MockBackend) -> Vec<WheelEvent> {
+        backend
+            .take_posted_events()
+            .into_iter()
+            .map(|event| match event {
+                UiohookEvent::Wheel(we) => we,
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect()
+    }
+
+    fn scroll(direction: WheelDirection) -> WheelEvent {
+        WheelEvent::builder()
+            .position(100, 200)
+            .rotation(-1)
+            .direction(direction)
+            .build()
+            .unwrap()
+    }
 
     #[test]
     fn test_wheel_event_from_bindings() {
@@ -125,40 +516,150 @@ mod tests {
         assert_eq!(wheel_event.clicks, 1);
         assert_eq!(wheel_event.x, 100);
         assert_eq!(wheel_event.y, 200);
-        assert_eq!(wheel_event.type_, bindings::WHEEL_UNIT_SCROLL as u8);
+        assert_eq!(wheel_event.scroll_type, WheelScrollType::Unit);
         assert_eq!(wheel_event.amount, 3);
         assert_eq!(wheel_event.rotation, -120);
-        assert_eq!(wheel_event.direction, WHEEL_VERTICAL_DIRECTION);
+        assert_eq!(wheel_event.direction, WheelDirection::Vertical);
+
+        let back = bindings::mouse_wheel_event_data::from(&wheel_event);
+        assert_eq!(back.type_, raw_event.type_);
+        assert_eq!(back.direction, raw_event.direction);
+        assert_eq!((back.amount, back.rotation), (raw_event.amount, raw_event.rotation));
     }
 
     #[test]
-    fn test_wheel_event_new() {
-        let wheel_event = WheelEvent::new(1, 100, 200, bindings::WHEEL_UNIT_SCROLL as u8, 3, -120, WHEEL_VERTICAL_DIRECTION);
+    fn test_unknown_wheel_values_are_preserved() {
+        let raw_event = bindings::mouse_wheel_event_data {
+            clicks: 1,
+            x: 0,
+            y: 0,
+            type_: 9,
+            amount: 1,
+            rotation: 1,
+            direction: 7,
+        };
 
-        assert_eq!(wheel_event.clicks, 1);
+        let wheel_event = WheelEvent::from(&raw_event);
+        assert_eq!(wheel_event.scroll_type, WheelScrollType::Other(9));
+        assert_eq!(wheel_event.direction, WheelDirection::Other(7));
+        assert!(!wheel_event.is_vertical() && !wheel_event.is_horizontal());
+
+        let back = bindings::mouse_wheel_event_data::from(&wheel_event);
+        assert_eq!((back.type_, back.direction), (9, 7));
+    }
+
+    #[test]
+    fn test_wheel_event_builder() {
+        let wheel_event = WheelEvent::builder()
+            .clicks(2)
+            .position(100, 200)
+            .scroll_type(WheelScrollType::Block)
+            .amount(1)
+            .rotation(2)
+            .direction(WheelDirection::Horizontal)
+            .build()
+            .unwrap();
+
+        assert_eq!(wheel_event.clicks, 2);
         assert_eq!(wheel_event.x, 100);
         assert_eq!(wheel_event.y, 200);
-        assert_eq!(wheel_event.type_, bindings::WHEEL_UNIT_SCROLL as u8);
-        assert_eq!(wheel_event.amount, 3);
-        assert_eq!(wheel_event.rotation, -120);
-        assert_eq!(wheel_event.direction, WHEEL_VERTICAL_DIRECTION);
+        assert_eq!(wheel_event.scroll_type, WheelScrollType::Block);
+        assert_eq!(wheel_event.amount, 1);
+        assert_eq!(wheel_event.rotation, 2);
+        assert_eq!(wheel_event.direction, WheelDirection::Horizontal);
+
+        assert_eq!(
+            WheelEvent::builder().build(),
+            Err(UiohookError::InvalidArgument("wheel rotation must not be zero"))
+        );
+        assert_eq!(
+            WheelEvent::builder().rotation(1).amount(0).build(),
+            Err(UiohookError::InvalidArgument("wheel amount must not be zero"))
+        );
     }
 
     #[test]
-    fn test_is_vertical() {
-        let vertical_event = WheelEvent::new(1, 100, 200, bindings::WHEEL_UNIT_SCROLL as u8, 3, -120, WHEEL_VERTICAL_DIRECTION);
-        let horizontal_event = WheelEvent::new(1, 100, 200, bindings::WHEEL_UNIT_SCROLL as u8, 3, -120, WHEEL_HORIZONTAL_DIRECTION);
+    fn test_raw_delta() {
+        assert_eq!(scroll(WheelDirection::Vertical).raw_delta(), -3);
+        let down = WheelEvent::builder().rotation(i16::MAX).amount(u16::MAX).build().unwrap();
+        assert_eq!(down.raw_delta(), i16::MAX as i32 * u16::MAX as i32);
+    }
+
+    #[test]
+    fn test_delta() {
+        let unit = |rotation| WheelEvent::builder().rotation(rotation).build().unwrap();
+        assert_eq!(unit(1).delta(), 3);
+        assert_eq!(unit(-1).delta(), -3);
+        assert_eq!(unit(WHEEL_DELTA).delta(), 3);
+        assert_eq!(unit(-WHEEL_DELTA).delta(), -3);
+        assert_eq!(unit(-2 * WHEEL_DELTA).delta(), -6);
+        assert_eq!(unit(-WHEEL_DELTA).raw_delta(), -360);
 
-        assert!(vertical_event.is_vertical());
-        assert!(!horizontal_event.is_vertical());
+        let block = |rotation| {
+            WheelEvent::builder()
+                .scroll_type(WheelScrollType::Block)
+                .amount(u16::MAX)
+                .rotation(rotation)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(block(1).delta(), 1);
+        assert_eq!(block(-WHEEL_DELTA).delta(), -1);
     }
 
     #[test]
-    fn test_is_horizontal() {
-        let vertical_event = WheelEvent::new(1, 100, 200, bindings::WHEEL_UNIT_SCROLL as u8, 3, -120, WHEEL_VERTICAL_DIRECTION);
-        let horizontal_event = WheelEvent::new(1, 100, 200, bindings::WHEEL_UNIT_SCROLL as u8, 3, -120, WHEEL_HORIZONTAL_DIRECTION);
+    fn test_raw_enum_conversion() {
+        assert_eq!(WheelScrollType::try_from(bindings::WHEEL_BLOCK_SCROLL as u8), Ok(WheelScrollType::Block));
+        assert_eq!(WheelDirection::try_from(WHEEL_HORIZONTAL_DIRECTION), Ok(WheelDirection::Horizontal));
+        assert!(WheelScrollType::try_from(0).is_err());
+        assert!(WheelDirection::try_from(0).is_err());
+        assert_eq!(u8::from(WheelDirection::Vertical), WHEEL_VERTICAL_DIRECTION);
+    }
 
-        assert!(!vertical_event.is_horizontal());
-        assert!(horizontal_event.is_horizontal());
+    #[test]
+    fn test_wheel_scroll() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        scroll_vertical(&hook, -2).unwrap();
+        scroll_horizontal(&hook, 1).unwrap();
+        assert_eq!(
+            wheel_scroll(&hook, WheelDirection::Vertical, 0),
+            Err(UiohookError::InvalidArgument("wheel rotation must not be zero"))
+        );
+
+        let posted = posted_wheel_events(&backend);
+        assert_eq!(posted.len(), 2);
+        assert_eq!((posted[0].direction, posted[0].rotation), (WheelDirection::Vertical, -2));
+        assert_eq!((posted[1].direction, posted[1].rotation), (WheelDirection::Horizontal, 1));
+    }
+
+    #[test]
+    fn test_smooth_scroll() {
+        let backend = MockBackend::new();
+        let hook = backend.hook();
+        let start = Instant::now();
+        smooth_scroll(&hook, WheelDirection::Vertical, -5, Duration::from_millis(40)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let posted = posted_wheel_events(&backend);
+        assert_eq!(posted.len(), 5);
+        assert!(posted.iter().all(|we| we.rotation == -1 && we.is_vertical()));
+        assert_eq!(posted.iter().map(WheelEvent::raw_delta).sum::<i32>(), -15);
+
+        smooth_scroll(&hook, WheelDirection::Horizontal, 1, Duration::from_secs(60)).unwrap();
+        assert_eq!(posted_wheel_events(&backend).len(), 1);
+        assert!(smooth_scroll(&hook, WheelDirection::Horizontal, 0, Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_is_vertical() {
+        assert!(scroll(WheelDirection::Vertical).is_vertical());
+        assert!(!scroll(WheelDirection::Horizontal).is_vertical());
+    }
+
+    #[test]
+    fn test_is_horizontal() {
+        assert!(!scroll(WheelDirection::Vertical).is_horizontal());
+        assert!(scroll(WheelDirection::Horizontal).is_horizontal());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,627 @@
+//! Global hotkeys built on top of the event dispatcher.
+//!
+//! A [`HotkeyManager`] holds a set of hotkeys such as `"Ctrl+Shift+K"` and is
+//! itself an [`EventFilter`], so it is attached like any other filter: pass a
+//! clone to [`Uiohook::with_filter`](crate::Uiohook::with_filter) or
+//! [`subscribe_filter`](crate::subscribe_filter). Hotkeys can be registered
+//! and unregistered while the hook is running.
+//!
+//! Modifier state is read from the [`Modifiers`] mask of each keyboard event,
+//! so no key state has to be tracked by hand.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use uiohook_rs::hotkey::HotkeyManager;
+//! use uiohook_rs::Uiohook;
+//!
+//! let hotkeys = HotkeyManager::new();
+//! hotkeys
+//!     .register("Ctrl+Shift+K", |_| println!("Ctrl+Shift+K pressed"))
+//!     .expect("Failed to register hotkey");
+//!
+//! let uiohook = Uiohook::with_filter(hotkeys.clone());
+//! uiohook.run().expect("Failed to run uiohook");
+//! ```
+
+use crate::error::{Result, UiohookError};
+use crate::hook::keyboard::{normalize_key_name, KeyCode, KeyboardEvent, KeyboardEventType};
+use crate::hook::modifiers::Modifiers;
+use crate::{EventFilter, EventVerdict, UiohookEvent};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+// Each modifier as (either side, left side, right side, name).
+const MODIFIER_KINDS: [(Modifiers, Modifiers, Modifiers, &str); 4] = [
+    (Modifiers::CTRL, Modifiers::CTRL_L, Modifiers::CTRL_R, "Ctrl"),
+    (Modifiers::ALT, Modifiers::ALT_L, Modifiers::ALT_R, "Alt"),
+    (Modifiers::SHIFT, Modifiers::SHIFT_L, Modifiers::SHIFT_R, "Shift"),
+    (Modifiers::META, Modifiers::META_L, Modifiers::META_R, "Meta"),
+];
+
+/// Identifies a hotkey registered with a [`HotkeyManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HotkeyId(u64);
+
+/// Decides which keystroke fires a hotkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HotkeyTrigger {
+    /// Fire when the key is pressed. Auto-repeated presses do not fire again.
+    #[default]
+    Press,
+    /// Fire when the key is released after a press that matched the hotkey.
+    Release,
+}
+
+/// A key combined with the modifiers that must be held, such as `Ctrl+Shift+K`.
+///
+/// A modifier given for both sides (e.g. `Modifiers::CTRL`) is satisfied by
+/// either key, while a single side (e.g. `Modifiers::CTRL_L`) requires that
+/// key. Modifiers that are not listed must not be held, so `Ctrl+K` does not
+/// fire for `Ctrl+Shift+K`. Lock states and mouse buttons are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hotkey::{Hotkey, HotkeyTrigger};
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::hook::modifiers::Modifiers;
+///
+/// let hotkey: Hotkey = "LeftCtrl+Shift+K".parse().expect("Failed to parse hotkey");
+/// assert_eq!(hotkey.key, KeyCode::K);
+/// assert_eq!(hotkey.modifiers, Modifiers::CTRL_L | Modifiers::SHIFT);
+/// assert_eq!(hotkey.to_string(), "LeftCtrl+Shift+K");
+///
+/// let hotkey = hotkey.with_trigger(HotkeyTrigger::Release).with_consume(true);
+/// assert!(hotkey.matches(KeyCode::K, Modifiers::CTRL_L | Modifiers::SHIFT_R));
+/// assert!(!hotkey.matches(KeyCode::K, Modifiers::CTRL_R | Modifiers::SHIFT_R));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    /// The key that fires the hotkey.
+    pub key: KeyCode,
+    /// The modifier keys that must be held.
+    pub modifiers: Modifiers,
+    /// Whether the hotkey fires on press or on release.
+    pub trigger: HotkeyTrigger,
+    /// Whether the keystrokes that fire the hotkey are consumed, so other
+    /// applications never see them. See [`EventVerdict::Consume`] for platform
+    /// support.
+    pub consume: bool,
+}
+
+impl Hotkey {
+    /// Create a hotkey that fires on press and lets the keystroke through.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key that fires the hotkey.
+    /// * `modifiers` - The modifier keys that must be held.
+    pub fn new(key: KeyCode, modifiers: Modifiers) -> Self {
+        Hotkey {
+            key,
+            modifiers: modifiers & Modifiers::KEYS,
+            trigger: HotkeyTrigger::Press,
+            consume: false,
+        }
+    }
+
+    /// Returns the hotkey with its trigger replaced.
+    pub fn with_trigger(mut self, trigger: HotkeyTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Returns the hotkey with consumption of its keystrokes turned on or off.
+    pub fn with_consume(mut self, consume: bool) -> Self {
+        self.consume = consume;
+        self
+    }
+
+    /// Returns `true` if pressing `key` while `modifiers` are held fires this hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key that was pressed.
+    /// * `modifiers` - The modifier state of the key event.
+    pub fn matches(&self, key: KeyCode, modifiers: Modifiers) -> bool {
+        if key != self.key {
+            return false;
+        }
+        // A modifier key may already report its own bit while it is pressed.
+        let held = (modifiers & Modifiers::KEYS).difference(modifier_of(key));
+        MODIFIER_KINDS.iter().all(|&(both, ..)| {
+            let required = self.modifiers & both;
+            let held = held & both;
+            if required == both {
+                !held.is_empty()
+            } else {
+                held == required
+            }
+        })
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = UiohookError;
+
+    /// Parses an accelerator string such as `"Ctrl+Shift+K"`.
+    ///
+    /// The last `+`-separated part is the key, parsed like [`KeyCode`]; the
+    /// others are modifiers. Modifier names ignore case and accept `Ctrl` or
+    /// `Control`, `Alt` or `Option`, `Shift`, and `Meta`, `Cmd`, `Super` or
+    /// `Win`. A `Left`/`L` or `Right`/`R` prefix or suffix (`LeftCtrl`, `RAlt`,
+    /// `ShiftL`) selects one side, and `AltGr` means the right Alt key.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let key = parts.pop().unwrap_or_default();
+        if key.trim().is_empty() {
+            return Err(UiohookError::InvalidArgument("hotkey has no key"));
+        }
+        let key = key.parse::<KeyCode>()?;
+
+        let mut modifiers = Modifiers::empty();
+        for part in parts {
+            modifiers |= parse_modifier(part).ok_or(UiohookError::InvalidArgument("unknown modifier name"))?;
+        }
+        Ok(Hotkey::new(key, modifiers))
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &(both, left, right, name) in &MODIFIER_KINDS {
+            let required = self.modifiers & both;
+            if required == left {
+                write!(f, "Left{}+", name)?;
+            } else if required == right {
+                write!(f, "Right{}+", name)?;
+            } else if required == both {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+// Parses one modifier of an accelerator string.
+fn parse_modifier(name: &str) -> Option<Modifiers> {
+    let name = normalize_key_name(name);
+    if name == "altgr" {
+        return Some(Modifiers::ALT_R);
+    }
+    if let Some((both, ..)) = modifier_kind(&name) {
+        return Some(both);
+    }
+
+    let sided = |base: &str, left: bool| {
+        modifier_kind(base).map(|(_, l, r, _)| if left { l } else { r })
+    };
+    for (side, left) in [("left", true), ("l", true), ("right", false), ("r", false)] {
+        let modifier = name
+            .strip_prefix(side)
+            .and_then(|base| sided(base, left))
+            .or_else(|| name.strip_suffix(side).and_then(|base| sided(base, left)));
+        if modifier.is_some() {
+            return modifier;
+        }
+    }
+    None
+}
+
+// Looks up a normalized modifier name without a side.
+fn modifier_kind(name: &str) -> Option<(Modifiers, Modifiers, Modifiers, &'static str)> {
+    let index = match name {
+        "ctrl" | "control" => 0,
+        "alt" | "option" | "opt" => 1,
+        "shift" => 2,
+        "meta" | "cmd" | "command" | "super" | "win" | "windows" => 3,
+        _ => return None,
+    };
+    Some(MODIFIER_KINDS[index])
+}
+
+// The modifier bit a key sets while it is held.
+fn modifier_of(key: KeyCode) -> Modifiers {
+    match key {
+        KeyCode::ShiftL => Modifiers::SHIFT_L,
+        KeyCode::ShiftR => Modifiers::SHIFT_R,
+        KeyCode::ControlL => Modifiers::CTRL_L,
+        KeyCode::ControlR => Modifiers::CTRL_R,
+        KeyCode::AltL => Modifiers::ALT_L,
+        KeyCode::AltR => Modifiers::ALT_R,
+        KeyCode::MetaL => Modifiers::META_L,
+        KeyCode::MetaR => Modifiers::META_R,
+        _ => Modifiers::empty(),
+    }
+}
+
+/// Callback invoked with the keystroke that fired a hotkey.
+type HotkeyCallback = Arc<dyn Fn(&KeyboardEvent) + Send + Sync>;
+
+struct Registration {
+    id: HotkeyId,
+    hotkey: Hotkey,
+    callback: HotkeyCallback,
+}
+
+// A held key whose press matched at least one hotkey.
+struct ActiveKey {
+    // Release-triggered hotkeys to fire when the key is released.
+    release: Vec<HotkeyId>,
+    consume: bool,
+}
+
+#[derive(Default)]
+struct ManagerInner {
+    next_id: AtomicU64,
+    hotkeys: RwLock<Vec<Registration>>,
+    active: Mutex<HashMap<KeyCode, ActiveKey>>,
+}
+
+/// A registry of hotkeys that fires callbacks from captured keyboard events.
+///
+/// Clones share the same registry, so one clone can be handed to the hook
+/// while another registers and unregisters hotkeys. Callbacks run on the hook
+/// thread and should return quickly.
+#[derive(Clone, Default)]
+pub struct HotkeyManager {
+    inner: Arc<ManagerInner>,
+}
+
+impl HotkeyManager {
+    /// Create an empty hotkey manager.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a hotkey from an accelerator string such as `"Ctrl+Shift+K"`.
+    ///
+    /// The hotkey fires on press and lets the keystroke through; use
+    /// [`HotkeyManager::register_hotkey`] for other behaviour.
+    ///
+    /// # Arguments
+    ///
+    /// * `accelerator` - The key combination, parsed as a [`Hotkey`].
+    /// * `callback` - Called with the key press that fired the hotkey.
+    ///
+    /// # Returns
+    ///
+    /// The id to pass to [`HotkeyManager::unregister`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if the accelerator cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hotkey::HotkeyManager;
+    ///
+    /// let hotkeys = HotkeyManager::new();
+    /// let id = hotkeys.register("Cmd+Space", |_| println!("Launcher")).expect("Failed to register hotkey");
+    /// assert!(hotkeys.unregister(id));
+    /// assert!(hotkeys.register("Ctrl+Hyper+K", |_| {}).is_err());
+    /// ```
+    pub fn register<F>(&self, accelerator: &str, callback: F) -> Result<HotkeyId>
+    where
+        F: Fn(&KeyboardEvent) + Send + Sync + 'static,
+    {
+        Ok(self.register_hotkey(accelerator.parse()?, callback))
+    }
+
+    /// Register a hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `hotkey` - The key combination, trigger and consumption behaviour.
+    /// * `callback` - Called with the keystroke that fired the hotkey.
+    ///
+    /// # Returns
+    ///
+    /// The id to pass to [`HotkeyManager::unregister`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hotkey::{Hotkey, HotkeyManager, HotkeyTrigger};
+    /// use uiohook_rs::hook::keyboard::KeyCode;
+    /// use uiohook_rs::hook::modifiers::Modifiers;
+    ///
+    /// let hotkeys = HotkeyManager::new();
+    /// let hotkey = Hotkey::new(KeyCode::F9, Modifiers::ALT_R)
+    ///     .with_trigger(HotkeyTrigger::Release)
+    ///     .with_consume(true);
+    /// hotkeys.register_hotkey(hotkey, |event| println!("{:?}", event));
+    /// ```
+    pub fn register_hotkey<F>(&self, hotkey: Hotkey, callback: F) -> HotkeyId
+    where
+        F: Fn(&KeyboardEvent) + Send + Sync + 'static,
+    {
+        let id = HotkeyId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        write(&self.inner.hotkeys).push(Registration {
+            id,
+            hotkey,
+            callback: Arc::new(callback),
+        });
+        id
+    }
+
+    /// Remove a registered hotkey.
+    ///
+    /// Returns `true` if the hotkey was registered.
+    pub fn unregister(&self, id: HotkeyId) -> bool {
+        let mut hotkeys = write(&self.inner.hotkeys);
+        let before = hotkeys.len();
+        hotkeys.retain(|registration| registration.id != id);
+        hotkeys.len() != before
+    }
+
+    /// Returns the hotkey registered under `id`.
+    pub fn hotkey(&self, id: HotkeyId) -> Option<Hotkey> {
+        read(&self.inner.hotkeys)
+            .iter()
+            .find(|registration| registration.id == id)
+            .map(|registration| registration.hotkey)
+    }
+
+    fn key_pressed(&self, event: &KeyboardEvent) -> EventVerdict {
+        let mut active = lock(&self.inner.active);
+        if let Some(pressed) = active.get(&event.key_code) {
+            // An auto-repeated press of a key that already fired.
+            return verdict(pressed.consume);
+        }
+
+        let mut callbacks = Vec::new();
+        let mut pressed = ActiveKey {
+            release: Vec::new(),
+            consume: false,
+        };
+        for registration in read(&self.inner.hotkeys).iter() {
+            if !registration.hotkey.matches(event.key_code, event.meta.modifiers) {
+                continue;
+            }
+            pressed.consume |= registration.hotkey.consume;
+            match registration.hotkey.trigger {
+                HotkeyTrigger::Press => callbacks.push(registration.callback.clone()),
+                HotkeyTrigger::Release => pressed.release.push(registration.id),
+            }
+        }
+        if callbacks.is_empty() && pressed.release.is_empty() {
+            return EventVerdict::Propagate;
+        }
+
+        let consume = pressed.consume;
+        active.insert(event.key_code, pressed);
+        drop(active);
+
+        // Callbacks run without locks held so they may (un)register hotkeys.
+        for callback in callbacks {
+            callback(event);
+        }
+        verdict(consume)
+    }
+
+    fn key_released(&self, event: &KeyboardEvent) -> EventVerdict {
+        let Some(pressed) = lock(&self.inner.active).remove(&event.key_code) else {
+            return EventVerdict::Propagate;
+        };
+
+        let callbacks: Vec<HotkeyCallback> = read(&self.inner.hotkeys)
+            .iter()
+            .filter(|registration| pressed.release.contains(&registration.id))
+            .map(|registration| registration.callback.clone())
+            .collect();
+        for callback in callbacks {
+            callback(event);
+        }
+        verdict(pressed.consume)
+    }
+}
+
+impl EventFilter for HotkeyManager {
+    fn filter_event(&self, event: &UiohookEvent) -> EventVerdict {
+        match event {
+            UiohookEvent::Keyboard(ke) => match ke.event_type {
+                KeyboardEventType::Pressed => self.key_pressed(ke),
+                KeyboardEventType::Released => self.key_released(ke),
+                KeyboardEventType::Typed => EventVerdict::Propagate,
+            },
+            // Releases are not delivered while the hook is down.
+            UiohookEvent::HookDisabled => {
+                lock(&self.inner.active).clear();
+                EventVerdict::Propagate
+            }
+            _ => EventVerdict::Propagate,
+        }
+    }
+}
+
+fn verdict(consume: bool) -> EventVerdict {
+    if consume {
+        EventVerdict::Consume
+    } else {
+        EventVerdict::Propagate
+    }
+}
+
+// Callbacks never run while these locks are held, so poisoning is ignored.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::backend::{HookBackend, MockBackend};
+    use crate::EventMeta;
+    use std::sync::atomic::AtomicUsize;
+
+    fn key_event(event_type: KeyboardEventType, key_code: KeyCode, modifiers: Modifiers) -> UiohookEvent {
+        UiohookEvent::Keyboard(KeyboardEvent {
+            event_type,
+            key_code,
+            raw_code: 0,
+            key_char: None,
+            meta: EventMeta::new(Default::default(), modifiers),
+        })
+    }
+
+    fn press(key_code: KeyCode, modifiers: Modifiers) -> UiohookEvent {
+        key_event(KeyboardEventType::Pressed, key_code, modifiers)
+    }
+
+    fn release(key_code: KeyCode, modifiers: Modifiers) -> UiohookEvent {
+        key_event(KeyboardEventType::Released, key_code, modifiers)
+    }
+
+    fn counter(hotkeys: &HotkeyManager, hotkey: Hotkey) -> (HotkeyId, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let fired = count.clone();
+        let id = hotkeys.register_hotkey(hotkey, move |_| {
+            fired.fetch_add(1, Ordering::SeqCst);
+        });
+        (id, count)
+    }
+
+    fn fired(count: &AtomicUsize) -> usize {
+        count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_parse_accelerators() {
+        let parse = |s: &str| s.parse::<Hotkey>().map(|h| (h.key, h.modifiers));
+
+        assert_eq!(parse("Ctrl+Shift+K"), Ok((KeyCode::K, Modifiers::CTRL | Modifiers::SHIFT)));
+        assert_eq!(parse("control + alt + delete"), Ok((KeyCode::Delete, Modifiers::CTRL | Modifiers::ALT)));
+        assert_eq!(parse("LCtrl+RShift+F5"), Ok((KeyCode::F5, Modifiers::CTRL_L | Modifiers::SHIFT_R)));
+        assert_eq!(parse("ShiftL+ControlR+A"), Ok((KeyCode::A, Modifiers::SHIFT_L | Modifiers::CTRL_R)));
+        assert_eq!(parse("RightCmd+Space"), Ok((KeyCode::Space, Modifiers::META_R)));
+        assert_eq!(parse("AltGr+E"), Ok((KeyCode::E, Modifiers::ALT_R)));
+        assert_eq!(parse("Super+Page Up"), Ok((KeyCode::PageUp, Modifiers::META)));
+        assert_eq!(parse("Escape"), Ok((KeyCode::Escape, Modifiers::empty())));
+
+        assert_eq!(parse(""), Err(UiohookError::InvalidArgument("hotkey has no key")));
+        assert_eq!(parse("Ctrl+"), Err(UiohookError::InvalidArgument("hotkey has no key")));
+        assert_eq!(parse("Hyper+K"), Err(UiohookError::InvalidArgument("unknown modifier name")));
+        assert_eq!(parse("Ctrl++K"), Err(UiohookError::InvalidArgument("unknown modifier name")));
+        assert_eq!(parse("Ctrl+Nope"), Err(UiohookError::InvalidArgument("unknown key name")));
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for accelerator in ["Ctrl+Shift+K", "LeftCtrl+RightAlt+Delete", "Meta+Page Up", "F12", "RightShift+0xE0F0"] {
+            let hotkey: Hotkey = accelerator.parse().unwrap();
+            assert_eq!(hotkey.to_string(), accelerator);
+        }
+    }
+
+    #[test]
+    fn test_matching() {
+        let either = Hotkey::new(KeyCode::K, Modifiers::CTRL | Modifiers::SHIFT);
+        assert!(either.matches(KeyCode::K, Modifiers::CTRL_L | Modifiers::SHIFT_L));
+        assert!(either.matches(KeyCode::K, Modifiers::CTRL_R | Modifiers::SHIFT));
+        assert!(!either.matches(KeyCode::J, Modifiers::CTRL_L | Modifiers::SHIFT_L));
+        assert!(!either.matches(KeyCode::K, Modifiers::CTRL_L));
+        assert!(!either.matches(KeyCode::K, Modifiers::CTRL_L | Modifiers::SHIFT_L | Modifiers::ALT_L));
+        // Lock states and mouse buttons do not matter.
+        assert!(either.matches(KeyCode::K, Modifiers::CTRL_L | Modifiers::SHIFT_L | Modifiers::CAPS_LOCK | Modifiers::BUTTON1));
+
+        let left = Hotkey::new(KeyCode::K, Modifiers::CTRL_L);
+        assert!(left.matches(KeyCode::K, Modifiers::CTRL_L));
+        assert!(!left.matches(KeyCode::K, Modifiers::CTRL_R));
+        assert!(!left.matches(KeyCode::K, Modifiers::CTRL));
+
+        // A modifier key's own bit is ignored.
+        let shift = Hotkey::new(KeyCode::ShiftR, Modifiers::CTRL);
+        assert!(shift.matches(KeyCode::ShiftR, Modifiers::CTRL_L | Modifiers::SHIFT_R));
+        assert!(!shift.matches(KeyCode::ShiftR, Modifiers::CTRL_L | Modifiers::SHIFT_L));
+    }
+
+    #[test]
+    fn test_press_trigger() {
+        let backend = MockBackend::new();
+        let hotkeys = HotkeyManager::new();
+        backend.dispatcher().subscribe_filter(hotkeys.clone());
+        let (_, count) = counter(&hotkeys, "Ctrl+Shift+K".parse().unwrap());
+        let held = Modifiers::CTRL_L | Modifiers::SHIFT_R;
+
+        assert_eq!(backend.inject(&press(KeyCode::K, held)), EventVerdict::Propagate);
+        assert_eq!(fired(&count), 1);
+        // Auto-repeat does not fire again.
+        backend.inject(&press(KeyCode::K, held));
+        assert_eq!(fired(&count), 1);
+        assert_eq!(backend.inject(&release(KeyCode::K, held)), EventVerdict::Propagate);
+
+        backend.inject(&press(KeyCode::K, Modifiers::CTRL_L));
+        backend.inject(&release(KeyCode::K, Modifiers::CTRL_L));
+        assert_eq!(fired(&count), 1);
+
+        backend.inject(&press(KeyCode::K, held));
+        assert_eq!(fired(&count), 2);
+    }
+
+    #[test]
+    fn test_release_trigger_and_consumption() {
+        let backend = MockBackend::new();
+        let hotkeys = HotkeyManager::new();
+        backend.dispatcher().subscribe_filter(hotkeys.clone());
+        let hotkey = Hotkey::new(KeyCode::F9, Modifiers::ALT_R)
+            .with_trigger(HotkeyTrigger::Release)
+            .with_consume(true);
+        let (_, count) = counter(&hotkeys, hotkey);
+
+        assert_eq!(backend.inject(&press(KeyCode::F9, Modifiers::ALT_R)), EventVerdict::Consume);
+        assert_eq!(backend.inject(&press(KeyCode::F9, Modifiers::ALT_R)), EventVerdict::Consume);
+        assert_eq!(fired(&count), 0);
+        // Alt was let go first; the release still belongs to the hotkey.
+        assert_eq!(backend.inject(&release(KeyCode::F9, Modifiers::empty())), EventVerdict::Consume);
+        assert_eq!(fired(&count), 1);
+
+        assert_eq!(backend.inject(&press(KeyCode::F9, Modifiers::ALT_L)), EventVerdict::Propagate);
+        assert_eq!(backend.inject(&release(KeyCode::F9, Modifiers::ALT_L)), EventVerdict::Propagate);
+        assert_eq!(fired(&count), 1);
+    }
+
+    #[test]
+    fn test_unregister() {
+        let backend = MockBackend::new();
+        let hotkeys = HotkeyManager::new();
+        backend.dispatcher().subscribe_filter(hotkeys.clone());
+        let (id, count) = counter(&hotkeys, Hotkey::new(KeyCode::Q, Modifiers::META));
+        let (_, other) = counter(&hotkeys, Hotkey::new(KeyCode::Q, Modifiers::META));
+
+        assert_eq!(hotkeys.hotkey(id), Some(Hotkey::new(KeyCode::Q, Modifiers::META)));
+        assert!(hotkeys.unregister(id));
+        assert!(!hotkeys.unregister(id));
+        assert_eq!(hotkeys.hotkey(id), None);
+
+        backend.inject(&press(KeyCode::Q, Modifiers::META_L));
+        assert_eq!(fired(&count), 0);
+        assert_eq!(fired(&other), 1);
+    }
+
+    #[test]
+    fn test_hook_disabled_resets_held_keys() {
+        let backend = MockBackend::new();
+        let hotkeys = HotkeyManager::new();
+        backend.dispatcher().subscribe_filter(hotkeys.clone());
+        let (_, count) = counter(&hotkeys, Hotkey::new(KeyCode::F1, Modifiers::empty()));
+
+        backend.inject(&press(KeyCode::F1, Modifiers::empty()));
+        backend.inject(&UiohookEvent::HookDisabled);
+        backend.inject(&press(KeyCode::F1, Modifiers::empty()));
+        assert_eq!(fired(&count), 2);
+    }
+}
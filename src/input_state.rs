@@ -0,0 +1,331 @@
+//! A live view of the input devices, kept up to date by the hook.
+//!
+//! [`InputState`] records which keys and mouse buttons are held, where the
+//! cursor is, the modifier and lock states of the latest event and when input
+//! was last seen. It is a cheap, cloneable handle: attach one clone to the
+//! hook and query the others from any thread.
+//!
+//! libuiohook reports `Dragged` events with [`MouseButton::NoButton`];
+//! [`InputState::resolve_button`] fills in the button being held instead.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use uiohook_rs::hook::keyboard::KeyCode;
+//! use uiohook_rs::{InputState, Uiohook};
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! let state = InputState::new();
+//! let uiohook = Uiohook::new(state.clone());
+//!
+//! thread::spawn(move || loop {
+//!     if state.is_key_held(KeyCode::Space) {
+//!         println!("Space is held at {:?}", state.cursor_position());
+//!     }
+//!     thread::sleep(Duration::from_millis(100));
+//! });
+//!
+//! uiohook.run().expect("Failed to run uiohook");
+//! ```
+
+use crate::hook::keyboard::{KeyCode, KeyboardEventType};
+use crate::hook::modifiers::Modifiers;
+use crate::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+use crate::{EventHandler, UiohookEvent};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct State {
+    // Both lists are in press order.
+    keys: Vec<KeyCode>,
+    buttons: Vec<MouseButton>,
+    cursor: Option<(i16, i16)>,
+    modifiers: Modifiers,
+    last_input: Option<Instant>,
+}
+
+/// Tracks the state of the keyboard and mouse from the events it handles.
+#[derive(Clone, Default)]
+pub struct InputState {
+    state: Arc<Mutex<State>>,
+}
+
+impl InputState {
+    /// Create a state with nothing held and no input seen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Update the state with an event.
+    ///
+    /// This is what the [`EventHandler`] implementation does; call it directly
+    /// when the events come from somewhere other than a hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event to record. `HookDisabled` releases everything,
+    ///   since releases are not delivered while the hook is down.
+    pub fn update(&self, event: &UiohookEvent) {
+        let mut state = self.lock();
+        if let Some(meta) = event.meta() {
+            state.modifiers = meta.modifiers;
+            state.last_input = Some(Instant::now());
+        }
+        match event {
+            UiohookEvent::Keyboard(ke) => match ke.event_type {
+                // Auto-repeat presses the same key again.
+                KeyboardEventType::Pressed if !state.keys.contains(&ke.key_code) => state.keys.push(ke.key_code),
+                KeyboardEventType::Released => state.keys.retain(|&key| key != ke.key_code),
+                _ => {}
+            },
+            UiohookEvent::Mouse(me) => {
+                state.cursor = Some((me.x, me.y));
+                match me.event_type {
+                    MouseEventType::Pressed
+                        if me.button != MouseButton::NoButton && !state.buttons.contains(&me.button) =>
+                    {
+                        state.buttons.push(me.button);
+                    }
+                    MouseEventType::Released => state.buttons.retain(|&button| button != me.button),
+                    _ => {}
+                }
+            }
+            UiohookEvent::Wheel(we) => state.cursor = Some((we.x, we.y)),
+            UiohookEvent::HookEnabled => {}
+            UiohookEvent::HookDisabled => {
+                state.keys.clear();
+                state.buttons.clear();
+                state.modifiers.remove(Modifiers::KEYS | Modifiers::BUTTONS);
+            }
+        }
+    }
+
+    /// Returns the keys currently held, in the order they were pressed.
+    pub fn held_keys(&self) -> Vec<KeyCode> {
+        self.lock().keys.clone()
+    }
+
+    /// Returns `true` if `key` is currently held.
+    pub fn is_key_held(&self, key: KeyCode) -> bool {
+        self.lock().keys.contains(&key)
+    }
+
+    /// Returns the mouse buttons currently held, in the order they were pressed.
+    pub fn held_buttons(&self) -> Vec<MouseButton> {
+        self.lock().buttons.clone()
+    }
+
+    /// Returns `true` if `button` is currently held.
+    pub fn is_button_held(&self, button: MouseButton) -> bool {
+        self.lock().buttons.contains(&button)
+    }
+
+    /// Returns the button a drag is made with: the first of the held buttons
+    /// to be pressed, or `None` if no button is held.
+    pub fn drag_button(&self) -> Option<MouseButton> {
+        self.lock().buttons.first().copied()
+    }
+
+    /// Returns the button of a mouse event, filling it in for drags.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - A mouse event seen by the hook.
+    ///
+    /// # Returns
+    ///
+    /// [`InputState::drag_button`] for a `Dragged` event without a button,
+    /// falling back to `NoButton`, and the event's own button otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::mouse::{MouseButton, MouseEvent, MouseEventType};
+    /// use uiohook_rs::{EventMeta, InputState, UiohookEvent};
+    ///
+    /// let state = InputState::new();
+    /// let event = |event_type, button| MouseEvent {
+    ///     event_type,
+    ///     button,
+    ///     clicks: 0,
+    ///     x: 0,
+    ///     y: 0,
+    ///     meta: EventMeta::default(),
+    /// };
+    ///
+    /// state.update(&UiohookEvent::Mouse(event(MouseEventType::Pressed, MouseButton::Button2)));
+    /// let drag = event(MouseEventType::Dragged, MouseButton::NoButton);
+    /// assert_eq!(state.resolve_button(&drag), MouseButton::Button2);
+    /// ```
+    pub fn resolve_button(&self, event: &MouseEvent) -> MouseButton {
+        if event.event_type == MouseEventType::Dragged && event.button == MouseButton::NoButton {
+            self.drag_button().unwrap_or(MouseButton::NoButton)
+        } else {
+            event.button
+        }
+    }
+
+    /// Returns the last known cursor position, or `None` before any mouse or
+    /// wheel event.
+    pub fn cursor_position(&self) -> Option<(i16, i16)> {
+        self.lock().cursor
+    }
+
+    /// Returns the modifier mask of the latest input event, including held
+    /// modifier keys, mouse buttons and lock states.
+    pub fn modifiers(&self) -> Modifiers {
+        self.lock().modifiers
+    }
+
+    /// Returns `true` if Caps Lock was on at the latest input event.
+    pub fn caps_lock(&self) -> bool {
+        self.modifiers().caps_lock()
+    }
+
+    /// Returns `true` if Num Lock was on at the latest input event.
+    pub fn num_lock(&self) -> bool {
+        self.modifiers().num_lock()
+    }
+
+    /// Returns `true` if Scroll Lock was on at the latest input event.
+    pub fn scroll_lock(&self) -> bool {
+        self.modifiers().scroll_lock()
+    }
+
+    /// Returns how long ago the latest input event was handled, or `None` if
+    /// there has been none.
+    pub fn time_since_last_input(&self) -> Option<Duration> {
+        self.lock().last_input.map(|instant| instant.elapsed())
+    }
+}
+
+impl EventHandler for InputState {
+    fn handle_event(&self, event: &UiohookEvent) {
+        self.update(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::backend::{HookBackend, MockBackend};
+    use crate::hook::keyboard::KeyboardEvent;
+    use crate::hook::wheel::WheelEvent;
+    use crate::EventMeta;
+
+    fn key_event(event_type: KeyboardEventType, key_code: KeyCode, modifiers: Modifiers) -> UiohookEvent {
+        UiohookEvent::Keyboard(KeyboardEvent {
+            event_type,
+            key_code,
+            raw_code: 0,
+            key_char: None,
+            meta: EventMeta::new(Duration::ZERO, modifiers),
+        })
+    }
+
+    fn mouse_event(event_type: MouseEventType, button: MouseButton, x: i16, y: i16) -> UiohookEvent {
+        UiohookEvent::Mouse(MouseEvent {
+            event_type,
+            button,
+            clicks: 0,
+            x,
+            y,
+            meta: EventMeta::default(),
+        })
+    }
+
+    #[test]
+    fn test_keys_and_modifiers() {
+        let state = InputState::new();
+        assert_eq!(state.time_since_last_input(), None);
+
+        state.update(&key_event(KeyboardEventType::Pressed, KeyCode::ShiftL, Modifiers::SHIFT_L));
+        state.update(&key_event(KeyboardEventType::Pressed, KeyCode::A, Modifiers::SHIFT_L | Modifiers::CAPS_LOCK));
+        // Auto-repeat.
+        state.update(&key_event(KeyboardEventType::Pressed, KeyCode::A, Modifiers::SHIFT_L | Modifiers::CAPS_LOCK));
+        state.update(&key_event(KeyboardEventType::Typed, KeyCode::A, Modifiers::SHIFT_L | Modifiers::CAPS_LOCK));
+        assert_eq!(state.held_keys(), vec![KeyCode::ShiftL, KeyCode::A]);
+        assert!(state.modifiers().shift_left());
+        assert!(state.caps_lock());
+        assert!(!state.num_lock());
+        assert!(!state.scroll_lock());
+        assert!(state.time_since_last_input().is_some());
+
+        state.update(&key_event(KeyboardEventType::Released, KeyCode::ShiftL, Modifiers::CAPS_LOCK));
+        assert!(!state.is_key_held(KeyCode::ShiftL));
+        assert!(state.is_key_held(KeyCode::A));
+        assert!(!state.modifiers().shift());
+    }
+
+    #[test]
+    fn test_buttons_and_drags() {
+        let state = InputState::new();
+        assert_eq!(state.cursor_position(), None);
+
+        state.update(&mouse_event(MouseEventType::Moved, MouseButton::NoButton, 5, 6));
+        assert_eq!(state.cursor_position(), Some((5, 6)));
+        assert_eq!(state.drag_button(), None);
+
+        state.update(&mouse_event(MouseEventType::Pressed, MouseButton::Button3, 5, 6));
+        state.update(&mouse_event(MouseEventType::Pressed, MouseButton::Button1, 5, 6));
+        assert_eq!(state.held_buttons(), vec![MouseButton::Button3, MouseButton::Button1]);
+
+        let drag = MouseEvent {
+            event_type: MouseEventType::Dragged,
+            button: MouseButton::NoButton,
+            clicks: 0,
+            x: 20,
+            y: 30,
+            meta: EventMeta::default(),
+        };
+        assert_eq!(state.resolve_button(&drag), MouseButton::Button3);
+        state.update(&UiohookEvent::Mouse(drag.clone()));
+        assert_eq!(state.cursor_position(), Some((20, 30)));
+
+        state.update(&mouse_event(MouseEventType::Released, MouseButton::Button3, 20, 30));
+        assert_eq!(state.resolve_button(&drag), MouseButton::Button1);
+        assert!(!state.is_button_held(MouseButton::Button3));
+        state.update(&mouse_event(MouseEventType::Released, MouseButton::Button1, 20, 30));
+        assert_eq!(state.resolve_button(&drag), MouseButton::NoButton);
+
+        // Events that name their button keep it.
+        let click = MouseEvent {
+            event_type: MouseEventType::Clicked,
+            button: MouseButton::Button2,
+            ..drag
+        };
+        assert_eq!(state.resolve_button(&click), MouseButton::Button2);
+
+        let wheel = WheelEvent::builder().position(-4, 9).rotation(1).build().unwrap();
+        state.update(&UiohookEvent::Wheel(wheel));
+        assert_eq!(state.cursor_position(), Some((-4, 9)));
+    }
+
+    #[test]
+    fn test_attached_to_hook() {
+        let backend = MockBackend::new();
+        let state = InputState::new();
+        backend.dispatcher().subscribe(state.clone());
+
+        backend.inject(&key_event(KeyboardEventType::Pressed, KeyCode::Space, Modifiers::NUM_LOCK));
+        assert!(state.num_lock());
+        backend.inject(&mouse_event(MouseEventType::Pressed, MouseButton::Button1, 1, 2));
+        let reader = state.clone();
+        let held = std::thread::spawn(move || (reader.held_keys(), reader.held_buttons()))
+            .join()
+            .unwrap();
+        assert_eq!(held, (vec![KeyCode::Space], vec![MouseButton::Button1]));
+
+        // Releases are lost while the hook is down.
+        backend.inject(&UiohookEvent::HookDisabled);
+        assert!(state.held_keys().is_empty());
+        assert!(state.held_buttons().is_empty());
+        assert_eq!(state.cursor_position(), Some((1, 2)));
+    }
+}
@@ -0,0 +1,244 @@
+//! Translation between `KeyCode` and other key numbering schemes.
+//!
+//! `KeyCode` mirrors libuiohook's virtual key codes, while
+//! `KeyboardEvent::raw_code` is whatever the platform reported. The submodules
+//! here map `KeyCode` to and from schemes that other tools log, so uiohook
+//! captures can be correlated with HID reports, evdev traces and X11 sessions:
+//!
+//! - [`hid`]: USB HID usage IDs on the Keyboard/Keypad page (0x07).
+//! - [`evdev`]: Linux input `KEY_*` codes.
+//! - [`x11`]: X11 keysyms.
+//! - [`w3c`]: W3C UI Events `code` and `key` strings, as used by browsers.
+//!
+//! Each numeric scheme is a one-to-one table: a key without a counterpart in a
+//! scheme (or one that would collide with another key) is simply absent, and
+//! translating it returns `None`.
+
+use crate::hook::keyboard::KeyCode;
+
+pub mod evdev;
+pub mod hid;
+pub mod w3c;
+pub mod x11;
+
+/// Looks up the value paired with `key` in a translation table.
+fn lookup<T: Copy>(table: &[(KeyCode, T)], key: KeyCode) -> Option<T> {
+    table.iter().find(|(k, _)| *k == key).map(|&(_, value)| value)
+}
+
+/// Looks up the key paired with `value` in a translation table.
+fn reverse_lookup<T: Copy + PartialEq>(table: &[(KeyCode, T)], value: T) -> Option<KeyCode> {
+    table.iter().find(|(_, v)| *v == value).map(|&(key, _)| key)
+}
+
+/// Asserts that a translation table pairs every key and every value at most once.
+#[cfg(test)]
+fn assert_bijective<T: Copy + PartialEq + std::fmt::Debug>(table: &[(KeyCode, T)]) {
+    for (i, &(key, value)) in table.iter().enumerate() {
+        assert!(!matches!(key, KeyCode::Other(_) | KeyCode::Undefined | KeyCode::CharUndefined));
+        for &(other_key, other_value) in &table[i + 1..] {
+            assert_ne!(key, other_key, "{:?} is listed twice", key);
+            assert_ne!(value, other_value, "{:?} is listed twice", value);
+        }
+        assert_eq!(lookup(table, key), Some(value));
+        assert_eq!(reverse_lookup(table, value), Some(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type PhysicalKey = (Option<u16>, Option<u16>, Option<&'static str>, &'static [u32]);
+
+    // One row per physical key: evdev code, HID usage, W3C `code` and the
+    // keysyms xkb's evdev rules assign to it. Evdev and HID columns follow the
+    // kernel's hid-input table and the `code` column the UI Events spec, except
+    // that HID Keyboard Clear (0x9C) is paired with KEY_CLEAR rather than the
+    // KEY_DELETE the kernel reports for it.
+    #[rustfmt::skip]
+    const PHYSICAL_KEYS: &[PhysicalKey] = &[
+        (Some(1), Some(0x29), Some("Escape"), &[0xFF1B]),
+        (Some(2), Some(0x1E), Some("Digit1"), &[0x31]),
+        (Some(3), Some(0x1F), Some("Digit2"), &[0x32]),
+        (Some(4), Some(0x20), Some("Digit3"), &[0x33]),
+        (Some(5), Some(0x21), Some("Digit4"), &[0x34]),
+        (Some(6), Some(0x22), Some("Digit5"), &[0x35]),
+        (Some(7), Some(0x23), Some("Digit6"), &[0x36]),
+        (Some(8), Some(0x24), Some("Digit7"), &[0x37]),
+        (Some(9), Some(0x25), Some("Digit8"), &[0x38]),
+        (Some(10), Some(0x26), Some("Digit9"), &[0x39]),
+        (Some(11), Some(0x27), Some("Digit0"), &[0x30]),
+        (Some(12), Some(0x2D), Some("Minus"), &[0x2D]),
+        (Some(13), Some(0x2E), Some("Equal"), &[0x3D]),
+        (Some(14), Some(0x2A), Some("Backspace"), &[0xFF08]),
+        (Some(15), Some(0x2B), Some("Tab"), &[0xFF09]),
+        (Some(30), Some(0x04), Some("KeyA"), &[0x61]),
+        (Some(48), Some(0x05), Some("KeyB"), &[0x62]),
+        (Some(46), Some(0x06), Some("KeyC"), &[0x63]),
+        (Some(32), Some(0x07), Some("KeyD"), &[0x64]),
+        (Some(18), Some(0x08), Some("KeyE"), &[0x65]),
+        (Some(33), Some(0x09), Some("KeyF"), &[0x66]),
+        (Some(34), Some(0x0A), Some("KeyG"), &[0x67]),
+        (Some(35), Some(0x0B), Some("KeyH"), &[0x68]),
+        (Some(23), Some(0x0C), Some("KeyI"), &[0x69]),
+        (Some(36), Some(0x0D), Some("KeyJ"), &[0x6A]),
+        (Some(37), Some(0x0E), Some("KeyK"), &[0x6B]),
+        (Some(38), Some(0x0F), Some("KeyL"), &[0x6C]),
+        (Some(50), Some(0x10), Some("KeyM"), &[0x6D]),
+        (Some(49), Some(0x11), Some("KeyN"), &[0x6E]),
+        (Some(24), Some(0x12), Some("KeyO"), &[0x6F]),
+        (Some(25), Some(0x13), Some("KeyP"), &[0x70]),
+        (Some(16), Some(0x14), Some("KeyQ"), &[0x71]),
+        (Some(19), Some(0x15), Some("KeyR"), &[0x72]),
+        (Some(31), Some(0x16), Some("KeyS"), &[0x73]),
+        (Some(20), Some(0x17), Some("KeyT"), &[0x74]),
+        (Some(22), Some(0x18), Some("KeyU"), &[0x75]),
+        (Some(47), Some(0x19), Some("KeyV"), &[0x76]),
+        (Some(17), Some(0x1A), Some("KeyW"), &[0x77]),
+        (Some(45), Some(0x1B), Some("KeyX"), &[0x78]),
+        (Some(21), Some(0x1C), Some("KeyY"), &[0x79]),
+        (Some(44), Some(0x1D), Some("KeyZ"), &[0x7A]),
+        (Some(26), Some(0x2F), Some("BracketLeft"), &[0x5B]),
+        (Some(27), Some(0x30), Some("BracketRight"), &[0x5D]),
+        (Some(28), Some(0x28), Some("Enter"), &[0xFF0D]),
+        (Some(29), Some(0xE0), Some("ControlLeft"), &[0xFFE3]),
+        (Some(39), Some(0x33), Some("Semicolon"), &[0x3B]),
+        (Some(40), Some(0x34), Some("Quote"), &[0x27]),
+        (Some(41), Some(0x35), Some("Backquote"), &[0x60]),
+        (Some(42), Some(0xE1), Some("ShiftLeft"), &[0xFFE1]),
+        (Some(43), Some(0x31), Some("Backslash"), &[0x5C]),
+        (Some(51), Some(0x36), Some("Comma"), &[0x2C]),
+        (Some(52), Some(0x37), Some("Period"), &[0x2E]),
+        (Some(53), Some(0x38), Some("Slash"), &[0x2F]),
+        (Some(54), Some(0xE5), Some("ShiftRight"), &[0xFFE2]),
+        (Some(55), Some(0x55), Some("NumpadMultiply"), &[0xFFAA]),
+        (Some(56), Some(0xE2), Some("AltLeft"), &[0xFFE9]),
+        (Some(57), Some(0x2C), Some("Space"), &[0x20]),
+        (Some(58), Some(0x39), Some("CapsLock"), &[0xFFE5]),
+        (Some(59), Some(0x3A), Some("F1"), &[0xFFBE]),
+        (Some(60), Some(0x3B), Some("F2"), &[0xFFBF]),
+        (Some(61), Some(0x3C), Some("F3"), &[0xFFC0]),
+        (Some(62), Some(0x3D), Some("F4"), &[0xFFC1]),
+        (Some(63), Some(0x3E), Some("F5"), &[0xFFC2]),
+        (Some(64), Some(0x3F), Some("F6"), &[0xFFC3]),
+        (Some(65), Some(0x40), Some("F7"), &[0xFFC4]),
+        (Some(66), Some(0x41), Some("F8"), &[0xFFC5]),
+        (Some(67), Some(0x42), Some("F9"), &[0xFFC6]),
+        (Some(68), Some(0x43), Some("F10"), &[0xFFC7]),
+        (Some(69), Some(0x53), Some("NumLock"), &[0xFF7F]),
+        (Some(70), Some(0x47), Some("ScrollLock"), &[0xFF14]),
+        (Some(71), Some(0x5F), Some("Numpad7"), &[0xFFB7, 0xFF95]),
+        (Some(72), Some(0x60), Some("Numpad8"), &[0xFFB8, 0xFF97]),
+        (Some(73), Some(0x61), Some("Numpad9"), &[0xFFB9, 0xFF9A]),
+        (Some(74), Some(0x56), Some("NumpadSubtract"), &[0xFFAD]),
+        (Some(75), Some(0x5C), Some("Numpad4"), &[0xFFB4, 0xFF96]),
+        (Some(76), Some(0x5D), Some("Numpad5"), &[0xFFB5, 0xFF9D]),
+        (Some(77), Some(0x5E), Some("Numpad6"), &[0xFFB6, 0xFF98]),
+        (Some(78), Some(0x57), Some("NumpadAdd"), &[0xFFAB]),
+        (Some(79), Some(0x59), Some("Numpad1"), &[0xFFB1, 0xFF9C]),
+        (Some(80), Some(0x5A), Some("Numpad2"), &[0xFFB2, 0xFF99]),
+        (Some(81), Some(0x5B), Some("Numpad3"), &[0xFFB3, 0xFF9B]),
+        (Some(82), Some(0x62), Some("Numpad0"), &[0xFFB0, 0xFF9E]),
+        (Some(83), Some(0x63), Some("NumpadDecimal"), &[0xFFAE, 0xFF9F]),
+        (Some(86), Some(0x64), Some("IntlBackslash"), &[]),
+        (Some(87), Some(0x44), Some("F11"), &[0xFFC8]),
+        (Some(88), Some(0x45), Some("F12"), &[0xFFC9]),
+        (Some(89), Some(0x87), Some("IntlRo"), &[]),
+        (Some(90), Some(0x92), Some("Lang3"), &[0xFF26]),
+        (Some(91), Some(0x93), Some("Lang4"), &[0xFF25]),
+        (Some(92), Some(0x8A), Some("Convert"), &[0xFF23]),
+        (Some(96), Some(0x58), Some("NumpadEnter"), &[0xFF8D]),
+        (Some(97), Some(0xE4), Some("ControlRight"), &[0xFFE4]),
+        (Some(98), Some(0x54), Some("NumpadDivide"), &[0xFFAF]),
+        (Some(99), Some(0x46), Some("PrintScreen"), &[0xFF61]),
+        (Some(100), Some(0xE6), Some("AltRight"), &[0xFFEA]),
+        (Some(102), Some(0x4A), Some("Home"), &[0xFF50]),
+        (Some(103), Some(0x52), Some("ArrowUp"), &[0xFF52]),
+        (Some(104), Some(0x4B), Some("PageUp"), &[0xFF55]),
+        (Some(105), Some(0x50), Some("ArrowLeft"), &[0xFF51]),
+        (Some(106), Some(0x4F), Some("ArrowRight"), &[0xFF53]),
+        (Some(107), Some(0x4D), Some("End"), &[0xFF57]),
+        (Some(108), Some(0x51), Some("ArrowDown"), &[0xFF54]),
+        (Some(109), Some(0x4E), Some("PageDown"), &[0xFF56]),
+        (Some(110), Some(0x49), Some("Insert"), &[0xFF63]),
+        (Some(111), Some(0x4C), Some("Delete"), &[0xFFFF]),
+        (Some(113), Some(0x7F), Some("AudioVolumeMute"), &[0x1008_FF12]),
+        (Some(114), Some(0x81), Some("AudioVolumeDown"), &[0x1008_FF11]),
+        (Some(115), Some(0x80), Some("AudioVolumeUp"), &[0x1008_FF13]),
+        (Some(116), Some(0x66), Some("Power"), &[0x1008_FF2A]),
+        (Some(117), Some(0x67), Some("NumpadEqual"), &[0xFFBD]),
+        (Some(119), Some(0x48), Some("Pause"), &[0xFF13]),
+        (Some(121), Some(0x85), Some("NumpadComma"), &[0xFFAC]),
+        (Some(124), Some(0x89), Some("IntlYen"), &[0xA5]),
+        (Some(125), Some(0xE3), Some("MetaLeft"), &[0xFFEB]),
+        (Some(126), Some(0xE7), Some("MetaRight"), &[0xFFEC]),
+        (Some(127), Some(0x65), Some("ContextMenu"), &[0xFF67]),
+        (Some(128), Some(0x78), None, &[0xFF69]),
+        (Some(129), Some(0x79), Some("Again"), &[0xFF66]),
+        (Some(130), None, Some("Props"), &[0x1005_FF70]),
+        (Some(131), Some(0x7A), Some("Undo"), &[0xFF65]),
+        (Some(132), None, None, &[0x1005_FF71]),
+        (Some(133), Some(0x7C), Some("Copy"), &[0x1008_FF57]),
+        (Some(134), None, Some("Open"), &[0x1005_FF73]),
+        (Some(135), Some(0x7D), Some("Paste"), &[0x1008_FF6D]),
+        (Some(136), Some(0x7E), Some("Find"), &[0xFF68]),
+        (Some(137), Some(0x7B), Some("Cut"), &[0x1008_FF58]),
+        (Some(138), Some(0x75), Some("Help"), &[0xFF6A]),
+        (Some(140), None, Some("LaunchApp2"), &[0x1008_FF1D]),
+        (Some(142), None, Some("Sleep"), &[0x1008_FF2F]),
+        (Some(143), None, Some("WakeUp"), &[0x1008_FF2B]),
+        (Some(155), None, Some("LaunchMail"), &[0x1008_FF19]),
+        (Some(156), None, Some("BrowserFavorites"), &[0x1008_FF30]),
+        (Some(158), None, Some("BrowserBack"), &[0x1008_FF26]),
+        (Some(159), None, Some("BrowserForward"), &[0x1008_FF27]),
+        (Some(161), None, Some("Eject"), &[0x1008_FF2C]),
+        (Some(163), None, Some("MediaTrackNext"), &[0x1008_FF17]),
+        (Some(164), None, Some("MediaPlayPause"), &[0x1008_FF14]),
+        (Some(165), None, Some("MediaTrackPrevious"), &[0x1008_FF16]),
+        (Some(166), None, Some("MediaStop"), &[0x1008_FF15]),
+        (Some(172), None, Some("BrowserHome"), &[0x1008_FF18]),
+        (Some(173), None, Some("BrowserRefresh"), &[0x1008_FF29]),
+        (Some(183), Some(0x68), Some("F13"), &[0xFFCA]),
+        (Some(184), Some(0x69), Some("F14"), &[0xFFCB]),
+        (Some(185), Some(0x6A), Some("F15"), &[0xFFCC]),
+        (Some(186), Some(0x6B), Some("F16"), &[0xFFCD]),
+        (Some(187), Some(0x6C), Some("F17"), &[0xFFCE]),
+        (Some(188), Some(0x6D), Some("F18"), &[0xFFCF]),
+        (Some(189), Some(0x6E), Some("F19"), &[0xFFD0]),
+        (Some(190), Some(0x6F), Some("F20"), &[0xFFD1]),
+        (Some(191), Some(0x70), Some("F21"), &[0xFFD2]),
+        (Some(192), Some(0x71), Some("F22"), &[0xFFD3]),
+        (Some(193), Some(0x72), Some("F23"), &[0xFFD4]),
+        (Some(194), Some(0x73), Some("F24"), &[0xFFD5]),
+        (Some(217), None, Some("BrowserSearch"), &[0x1008_FF1B]),
+        (Some(226), None, Some("MediaSelect"), &[0x1008_FF32]),
+        (Some(355), Some(0x9C), None, &[0xFF0B]),
+        (None, None, Some("BrowserStop"), &[0x1008_FF28]),
+        (None, None, None, &[0x1008_FF91]),
+        (None, None, None, &[0x1008_FF92]),
+    ];
+
+    /// Returns the index of the `PHYSICAL_KEYS` row a translated value belongs to.
+    fn row(matches: impl Fn(&PhysicalKey) -> bool) -> Option<usize> {
+        PHYSICAL_KEYS.iter().position(matches)
+    }
+
+    #[test]
+    fn test_tables_agree_on_physical_keys() {
+        for key in KeyCode::all() {
+            let rows = [
+                evdev::to_code(key).map(|code| (row(|r| r.0 == Some(code)), "evdev")),
+                hid::to_usage(key).map(|usage| (row(|r| r.1 == Some(usage)), "HID")),
+                w3c::to_code(key).map(|code| (row(|r| r.2 == Some(code)), "W3C")),
+                x11::to_keysym(key).map(|keysym| (row(|r| r.3.contains(&keysym)), "X11")),
+            ];
+
+            let mut physical = None;
+            for (row, scheme) in rows.into_iter().flatten() {
+                let row = row.unwrap_or_else(|| panic!("{:?} has an unknown {} value", key, scheme));
+                assert_eq!(*physical.get_or_insert(row), row, "{:?} names another key in {}", key, scheme);
+            }
+        }
+    }
+}
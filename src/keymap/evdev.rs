@@ -0,0 +1,126 @@
+//! Linux evdev key codes.
+//!
+//! Codes are the `KEY_*` values from `linux/input-event-codes.h`, as reported
+//! in the `code` field of `EV_KEY` input events (and printed by `evtest`).
+
+use crate::hook::keyboard::KeyCode;
+
+#[rustfmt::skip]
+const TABLE: &[(KeyCode, u16)] = &[
+    (KeyCode::Escape, 1),
+    (KeyCode::Num1, 2), (KeyCode::Num2, 3), (KeyCode::Num3, 4), (KeyCode::Num4, 5),
+    (KeyCode::Num5, 6), (KeyCode::Num6, 7), (KeyCode::Num7, 8), (KeyCode::Num8, 9),
+    (KeyCode::Num9, 10), (KeyCode::Num0, 11),
+    (KeyCode::Minus, 12), (KeyCode::Equals, 13), (KeyCode::Backspace, 14), (KeyCode::Tab, 15),
+    (KeyCode::Q, 16), (KeyCode::W, 17), (KeyCode::E, 18), (KeyCode::R, 19), (KeyCode::T, 20),
+    (KeyCode::Y, 21), (KeyCode::U, 22), (KeyCode::I, 23), (KeyCode::O, 24), (KeyCode::P, 25),
+    (KeyCode::OpenBracket, 26), (KeyCode::CloseBracket, 27), (KeyCode::Enter, 28),
+    (KeyCode::ControlL, 29),
+    (KeyCode::A, 30), (KeyCode::S, 31), (KeyCode::D, 32), (KeyCode::F, 33), (KeyCode::G, 34),
+    (KeyCode::H, 35), (KeyCode::J, 36), (KeyCode::K, 37), (KeyCode::L, 38),
+    (KeyCode::Semicolon, 39), (KeyCode::Quote, 40), (KeyCode::Backquote, 41),
+    (KeyCode::ShiftL, 42), (KeyCode::Backslash, 43),
+    (KeyCode::Z, 44), (KeyCode::X, 45), (KeyCode::C, 46), (KeyCode::V, 47), (KeyCode::B, 48),
+    (KeyCode::N, 49), (KeyCode::M, 50),
+    (KeyCode::Comma, 51), (KeyCode::Period, 52), (KeyCode::Slash, 53), (KeyCode::ShiftR, 54),
+    (KeyCode::KpMultiply, 55), (KeyCode::AltL, 56), (KeyCode::Space, 57), (KeyCode::CapsLock, 58),
+    (KeyCode::F1, 59), (KeyCode::F2, 60), (KeyCode::F3, 61), (KeyCode::F4, 62), (KeyCode::F5, 63),
+    (KeyCode::F6, 64), (KeyCode::F7, 65), (KeyCode::F8, 66), (KeyCode::F9, 67), (KeyCode::F10, 68),
+    (KeyCode::NumLock, 69), (KeyCode::ScrollLock, 70),
+    (KeyCode::Kp7, 71), (KeyCode::Kp8, 72), (KeyCode::Kp9, 73), (KeyCode::KpSubtract, 74),
+    (KeyCode::Kp4, 75), (KeyCode::Kp5, 76), (KeyCode::Kp6, 77), (KeyCode::KpAdd, 78),
+    (KeyCode::Kp1, 79), (KeyCode::Kp2, 80), (KeyCode::Kp3, 81), (KeyCode::Kp0, 82),
+    (KeyCode::KpSeparator, 83), (KeyCode::LesserGreater, 86), (KeyCode::F11, 87), (KeyCode::F12, 88),
+    (KeyCode::Underscore, 89), (KeyCode::Katakana, 90), (KeyCode::Hiragana, 91), (KeyCode::Kanji, 92),
+    (KeyCode::KpEnter, 96), (KeyCode::ControlR, 97), (KeyCode::KpDivide, 98),
+    (KeyCode::PrintScreen, 99), (KeyCode::AltR, 100),
+    (KeyCode::Home, 102), (KeyCode::Up, 103), (KeyCode::PageUp, 104), (KeyCode::Left, 105),
+    (KeyCode::Right, 106), (KeyCode::End, 107), (KeyCode::Down, 108), (KeyCode::PageDown, 109),
+    (KeyCode::Insert, 110), (KeyCode::Delete, 111),
+    (KeyCode::VolumeMute, 113), (KeyCode::VolumeDown, 114), (KeyCode::VolumeUp, 115),
+    (KeyCode::Power, 116), (KeyCode::KpEquals, 117), (KeyCode::Pause, 119), (KeyCode::KpComma, 121),
+    (KeyCode::Yen, 124), (KeyCode::MetaL, 125), (KeyCode::MetaR, 126), (KeyCode::ContextMenu, 127),
+    (KeyCode::SunStop, 128), (KeyCode::SunAgain, 129), (KeyCode::SunProps, 130),
+    (KeyCode::SunUndo, 131), (KeyCode::SunFront, 132), (KeyCode::SunCopy, 133),
+    (KeyCode::SunOpen, 134), (KeyCode::SunInsert, 135), (KeyCode::SunFind, 136),
+    (KeyCode::SunCut, 137), (KeyCode::SunHelp, 138),
+    (KeyCode::AppCalculator, 140), (KeyCode::Sleep, 142), (KeyCode::Wake, 143),
+    (KeyCode::AppMail, 155), (KeyCode::BrowserFavorites, 156),
+    (KeyCode::BrowserBack, 158), (KeyCode::BrowserForward, 159), (KeyCode::MediaEject, 161),
+    (KeyCode::MediaNext, 163), (KeyCode::MediaPlay, 164), (KeyCode::MediaPrevious, 165),
+    (KeyCode::MediaStop, 166), (KeyCode::BrowserHome, 172), (KeyCode::BrowserRefresh, 173),
+    (KeyCode::F13, 183), (KeyCode::F14, 184), (KeyCode::F15, 185), (KeyCode::F16, 186),
+    (KeyCode::F17, 187), (KeyCode::F18, 188), (KeyCode::F19, 189), (KeyCode::F20, 190),
+    (KeyCode::F21, 191), (KeyCode::F22, 192), (KeyCode::F23, 193), (KeyCode::F24, 194),
+    (KeyCode::BrowserSearch, 217), (KeyCode::MediaSelect, 226), (KeyCode::Clear, 355),
+];
+
+/// Translates a key code to a Linux evdev `KEY_*` code.
+///
+/// # Arguments
+///
+/// * `key` - The key to translate.
+///
+/// # Returns
+///
+/// The evdev code, or `None` if the key has no evdev equivalent.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::evdev;
+///
+/// // KEY_A
+/// assert_eq!(evdev::to_code(KeyCode::A), Some(30));
+/// ```
+pub fn to_code(key: KeyCode) -> Option<u16> {
+    super::lookup(TABLE, key)
+}
+
+/// Translates a Linux evdev `KEY_*` code to a key code.
+///
+/// # Arguments
+///
+/// * `code` - The evdev code.
+///
+/// # Returns
+///
+/// The key code, or `None` if the evdev code has no `KeyCode` equivalent.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::evdev;
+///
+/// // KEY_LEFTMETA
+/// assert_eq!(evdev::from_code(125), Some(KeyCode::MetaL));
+/// ```
+pub fn from_code(code: u16) -> Option<KeyCode> {
+    super::reverse_lookup(TABLE, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_bijective() {
+        super::super::assert_bijective(TABLE);
+    }
+
+    #[test]
+    fn test_known_codes() {
+        assert_eq!(to_code(KeyCode::Escape), Some(1));
+        assert_eq!(to_code(KeyCode::Enter), Some(28));
+        assert_eq!(to_code(KeyCode::F24), Some(194));
+        assert_eq!(from_code(99), Some(KeyCode::PrintScreen));
+        // VC_KANJI is the JIS Henkan key, KEY_HENKAN, not KEY_HANJA.
+        assert_eq!(to_code(KeyCode::Kanji), Some(92));
+        assert_eq!(from_code(123), None);
+        assert_eq!(from_code(0), None);
+        assert_eq!(to_code(KeyCode::KpHome), None);
+    }
+
+}
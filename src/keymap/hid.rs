@@ -0,0 +1,123 @@
+//! USB HID usage IDs.
+//!
+//! Usages are taken from the Keyboard/Keypad usage page (0x07) of the HID
+//! Usage Tables. Keys that live on other pages, such as the media and browser
+//! keys on the Consumer page, are not translated.
+
+use crate::hook::keyboard::KeyCode;
+
+/// The HID usage page that every usage in this module belongs to.
+pub const KEYBOARD_PAGE: u16 = 0x07;
+
+#[rustfmt::skip]
+const TABLE: &[(KeyCode, u16)] = &[
+    (KeyCode::A, 0x04), (KeyCode::B, 0x05), (KeyCode::C, 0x06), (KeyCode::D, 0x07),
+    (KeyCode::E, 0x08), (KeyCode::F, 0x09), (KeyCode::G, 0x0A), (KeyCode::H, 0x0B),
+    (KeyCode::I, 0x0C), (KeyCode::J, 0x0D), (KeyCode::K, 0x0E), (KeyCode::L, 0x0F),
+    (KeyCode::M, 0x10), (KeyCode::N, 0x11), (KeyCode::O, 0x12), (KeyCode::P, 0x13),
+    (KeyCode::Q, 0x14), (KeyCode::R, 0x15), (KeyCode::S, 0x16), (KeyCode::T, 0x17),
+    (KeyCode::U, 0x18), (KeyCode::V, 0x19), (KeyCode::W, 0x1A), (KeyCode::X, 0x1B),
+    (KeyCode::Y, 0x1C), (KeyCode::Z, 0x1D),
+    (KeyCode::Num1, 0x1E), (KeyCode::Num2, 0x1F), (KeyCode::Num3, 0x20), (KeyCode::Num4, 0x21),
+    (KeyCode::Num5, 0x22), (KeyCode::Num6, 0x23), (KeyCode::Num7, 0x24), (KeyCode::Num8, 0x25),
+    (KeyCode::Num9, 0x26), (KeyCode::Num0, 0x27),
+    (KeyCode::Enter, 0x28), (KeyCode::Escape, 0x29), (KeyCode::Backspace, 0x2A), (KeyCode::Tab, 0x2B),
+    (KeyCode::Space, 0x2C), (KeyCode::Minus, 0x2D), (KeyCode::Equals, 0x2E),
+    (KeyCode::OpenBracket, 0x2F), (KeyCode::CloseBracket, 0x30), (KeyCode::Backslash, 0x31),
+    (KeyCode::Semicolon, 0x33), (KeyCode::Quote, 0x34), (KeyCode::Backquote, 0x35),
+    (KeyCode::Comma, 0x36), (KeyCode::Period, 0x37), (KeyCode::Slash, 0x38),
+    (KeyCode::CapsLock, 0x39),
+    (KeyCode::F1, 0x3A), (KeyCode::F2, 0x3B), (KeyCode::F3, 0x3C), (KeyCode::F4, 0x3D),
+    (KeyCode::F5, 0x3E), (KeyCode::F6, 0x3F), (KeyCode::F7, 0x40), (KeyCode::F8, 0x41),
+    (KeyCode::F9, 0x42), (KeyCode::F10, 0x43), (KeyCode::F11, 0x44), (KeyCode::F12, 0x45),
+    (KeyCode::PrintScreen, 0x46), (KeyCode::ScrollLock, 0x47), (KeyCode::Pause, 0x48),
+    (KeyCode::Insert, 0x49), (KeyCode::Home, 0x4A), (KeyCode::PageUp, 0x4B),
+    (KeyCode::Delete, 0x4C), (KeyCode::End, 0x4D), (KeyCode::PageDown, 0x4E),
+    (KeyCode::Right, 0x4F), (KeyCode::Left, 0x50), (KeyCode::Down, 0x51), (KeyCode::Up, 0x52),
+    (KeyCode::NumLock, 0x53), (KeyCode::KpDivide, 0x54), (KeyCode::KpMultiply, 0x55),
+    (KeyCode::KpSubtract, 0x56), (KeyCode::KpAdd, 0x57), (KeyCode::KpEnter, 0x58),
+    (KeyCode::Kp1, 0x59), (KeyCode::Kp2, 0x5A), (KeyCode::Kp3, 0x5B), (KeyCode::Kp4, 0x5C),
+    (KeyCode::Kp5, 0x5D), (KeyCode::Kp6, 0x5E), (KeyCode::Kp7, 0x5F), (KeyCode::Kp8, 0x60),
+    (KeyCode::Kp9, 0x61), (KeyCode::Kp0, 0x62), (KeyCode::KpSeparator, 0x63),
+    (KeyCode::LesserGreater, 0x64), (KeyCode::ContextMenu, 0x65), (KeyCode::Power, 0x66),
+    (KeyCode::KpEquals, 0x67),
+    (KeyCode::F13, 0x68), (KeyCode::F14, 0x69), (KeyCode::F15, 0x6A), (KeyCode::F16, 0x6B),
+    (KeyCode::F17, 0x6C), (KeyCode::F18, 0x6D), (KeyCode::F19, 0x6E), (KeyCode::F20, 0x6F),
+    (KeyCode::F21, 0x70), (KeyCode::F22, 0x71), (KeyCode::F23, 0x72), (KeyCode::F24, 0x73),
+    (KeyCode::SunHelp, 0x75), (KeyCode::SunStop, 0x78), (KeyCode::SunAgain, 0x79),
+    (KeyCode::SunUndo, 0x7A), (KeyCode::SunCut, 0x7B), (KeyCode::SunCopy, 0x7C),
+    (KeyCode::SunInsert, 0x7D), (KeyCode::SunFind, 0x7E),
+    (KeyCode::VolumeMute, 0x7F), (KeyCode::VolumeUp, 0x80), (KeyCode::VolumeDown, 0x81),
+    (KeyCode::KpComma, 0x85), (KeyCode::Underscore, 0x87), (KeyCode::Yen, 0x89), (KeyCode::Kanji, 0x8A),
+    (KeyCode::Katakana, 0x92), (KeyCode::Hiragana, 0x93), (KeyCode::Clear, 0x9C),
+    (KeyCode::ControlL, 0xE0), (KeyCode::ShiftL, 0xE1), (KeyCode::AltL, 0xE2), (KeyCode::MetaL, 0xE3),
+    (KeyCode::ControlR, 0xE4), (KeyCode::ShiftR, 0xE5), (KeyCode::AltR, 0xE6), (KeyCode::MetaR, 0xE7),
+];
+
+/// Translates a key code to its usage ID on the HID Keyboard/Keypad page.
+///
+/// # Arguments
+///
+/// * `key` - The key to translate.
+///
+/// # Returns
+///
+/// The usage ID, or `None` if the key has no usage on [`KEYBOARD_PAGE`].
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::hid;
+///
+/// assert_eq!(hid::to_usage(KeyCode::A), Some(0x04));
+/// assert_eq!(hid::to_usage(KeyCode::MediaPlay), None);
+/// ```
+pub fn to_usage(key: KeyCode) -> Option<u16> {
+    super::lookup(TABLE, key)
+}
+
+/// Translates a usage ID on the HID Keyboard/Keypad page to a key code.
+///
+/// # Arguments
+///
+/// * `usage` - The usage ID, without the page.
+///
+/// # Returns
+///
+/// The key code, or `None` if the usage has no `KeyCode` equivalent.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::hid;
+///
+/// assert_eq!(hid::from_usage(0xE1), Some(KeyCode::ShiftL));
+/// ```
+pub fn from_usage(usage: u16) -> Option<KeyCode> {
+    super::reverse_lookup(TABLE, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_bijective() {
+        super::super::assert_bijective(TABLE);
+    }
+
+    #[test]
+    fn test_known_usages() {
+        assert_eq!(to_usage(KeyCode::Escape), Some(0x29));
+        assert_eq!(to_usage(KeyCode::F24), Some(0x73));
+        assert_eq!(to_usage(KeyCode::MetaR), Some(0xE7));
+        assert_eq!(from_usage(0x63), Some(KeyCode::KpSeparator));
+        // Keyboard International4, the JIS Henkan key.
+        assert_eq!(to_usage(KeyCode::Kanji), Some(0x8A));
+        assert_eq!(from_usage(0x00), None);
+        assert_eq!(to_usage(KeyCode::Other(0x04)), None);
+        assert_eq!(to_usage(KeyCode::KpEnd), None);
+    }
+}
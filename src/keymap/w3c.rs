@@ -0,0 +1,468 @@
+//! W3C UI Events `code` and `key` strings.
+//!
+//! Browsers describe keyboard input with two strings: `code` names the
+//! physical key (`"KeyA"`, `"ShiftLeft"`, `"Numpad5"`) and `key` names what the
+//! key produced (`"a"`, `"A"`, `"Shift"`, `"ArrowUp"`). This module converts
+//! between those strings and [`KeyCode`], so captured events can be fed to web
+//! tooling and browser-recorded sessions can be replayed with
+//! [`Uiohook::post_event`](crate::Uiohook::post_event).
+//!
+//! Where a `key` value depends on the keyboard layout, a US QWERTY layout is
+//! assumed.
+
+use crate::error::{Result, UiohookError};
+use crate::hook::keyboard::{us_layout_key, KeyCode, KeyboardEvent, KeyboardEventType};
+use crate::hook::modifiers::Modifiers;
+use crate::EventMeta;
+
+/// The `key` value for a key whose meaning is unknown.
+pub const UNIDENTIFIED: &str = "Unidentified";
+
+#[rustfmt::skip]
+const CODES: &[(KeyCode, &str)] = &[
+    (KeyCode::Escape, "Escape"),
+    (KeyCode::F1, "F1"), (KeyCode::F2, "F2"), (KeyCode::F3, "F3"), (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"), (KeyCode::F6, "F6"), (KeyCode::F7, "F7"), (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"), (KeyCode::F10, "F10"), (KeyCode::F11, "F11"), (KeyCode::F12, "F12"),
+    (KeyCode::F13, "F13"), (KeyCode::F14, "F14"), (KeyCode::F15, "F15"), (KeyCode::F16, "F16"),
+    (KeyCode::F17, "F17"), (KeyCode::F18, "F18"), (KeyCode::F19, "F19"), (KeyCode::F20, "F20"),
+    (KeyCode::F21, "F21"), (KeyCode::F22, "F22"), (KeyCode::F23, "F23"), (KeyCode::F24, "F24"),
+    (KeyCode::Backquote, "Backquote"),
+    (KeyCode::Num1, "Digit1"), (KeyCode::Num2, "Digit2"), (KeyCode::Num3, "Digit3"),
+    (KeyCode::Num4, "Digit4"), (KeyCode::Num5, "Digit5"), (KeyCode::Num6, "Digit6"),
+    (KeyCode::Num7, "Digit7"), (KeyCode::Num8, "Digit8"), (KeyCode::Num9, "Digit9"),
+    (KeyCode::Num0, "Digit0"),
+    (KeyCode::Minus, "Minus"), (KeyCode::Equals, "Equal"), (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Tab, "Tab"), (KeyCode::CapsLock, "CapsLock"),
+    (KeyCode::A, "KeyA"), (KeyCode::B, "KeyB"), (KeyCode::C, "KeyC"), (KeyCode::D, "KeyD"),
+    (KeyCode::E, "KeyE"), (KeyCode::F, "KeyF"), (KeyCode::G, "KeyG"), (KeyCode::H, "KeyH"),
+    (KeyCode::I, "KeyI"), (KeyCode::J, "KeyJ"), (KeyCode::K, "KeyK"), (KeyCode::L, "KeyL"),
+    (KeyCode::M, "KeyM"), (KeyCode::N, "KeyN"), (KeyCode::O, "KeyO"), (KeyCode::P, "KeyP"),
+    (KeyCode::Q, "KeyQ"), (KeyCode::R, "KeyR"), (KeyCode::S, "KeyS"), (KeyCode::T, "KeyT"),
+    (KeyCode::U, "KeyU"), (KeyCode::V, "KeyV"), (KeyCode::W, "KeyW"), (KeyCode::X, "KeyX"),
+    (KeyCode::Y, "KeyY"), (KeyCode::Z, "KeyZ"),
+    (KeyCode::OpenBracket, "BracketLeft"), (KeyCode::CloseBracket, "BracketRight"),
+    (KeyCode::Backslash, "Backslash"), (KeyCode::Semicolon, "Semicolon"), (KeyCode::Quote, "Quote"),
+    (KeyCode::Enter, "Enter"), (KeyCode::Comma, "Comma"), (KeyCode::Period, "Period"),
+    (KeyCode::Slash, "Slash"), (KeyCode::Space, "Space"),
+    (KeyCode::PrintScreen, "PrintScreen"), (KeyCode::ScrollLock, "ScrollLock"),
+    (KeyCode::Pause, "Pause"), (KeyCode::LesserGreater, "IntlBackslash"),
+    (KeyCode::Insert, "Insert"), (KeyCode::Delete, "Delete"), (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"), (KeyCode::PageUp, "PageUp"), (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Up, "ArrowUp"), (KeyCode::Left, "ArrowLeft"), (KeyCode::Right, "ArrowRight"),
+    (KeyCode::Down, "ArrowDown"),
+    (KeyCode::NumLock, "NumLock"), (KeyCode::KpDivide, "NumpadDivide"),
+    (KeyCode::KpMultiply, "NumpadMultiply"), (KeyCode::KpSubtract, "NumpadSubtract"),
+    (KeyCode::KpEquals, "NumpadEqual"), (KeyCode::KpAdd, "NumpadAdd"),
+    (KeyCode::KpEnter, "NumpadEnter"), (KeyCode::KpSeparator, "NumpadDecimal"),
+    (KeyCode::KpComma, "NumpadComma"),
+    (KeyCode::Kp1, "Numpad1"), (KeyCode::Kp2, "Numpad2"), (KeyCode::Kp3, "Numpad3"),
+    (KeyCode::Kp4, "Numpad4"), (KeyCode::Kp5, "Numpad5"), (KeyCode::Kp6, "Numpad6"),
+    (KeyCode::Kp7, "Numpad7"), (KeyCode::Kp8, "Numpad8"), (KeyCode::Kp9, "Numpad9"),
+    (KeyCode::Kp0, "Numpad0"),
+    (KeyCode::ShiftL, "ShiftLeft"), (KeyCode::ShiftR, "ShiftRight"),
+    (KeyCode::ControlL, "ControlLeft"), (KeyCode::ControlR, "ControlRight"),
+    (KeyCode::AltL, "AltLeft"), (KeyCode::AltR, "AltRight"),
+    (KeyCode::MetaL, "MetaLeft"), (KeyCode::MetaR, "MetaRight"),
+    (KeyCode::ContextMenu, "ContextMenu"), (KeyCode::Power, "Power"), (KeyCode::Sleep, "Sleep"),
+    (KeyCode::Wake, "WakeUp"),
+    (KeyCode::MediaPlay, "MediaPlayPause"), (KeyCode::MediaStop, "MediaStop"),
+    (KeyCode::MediaPrevious, "MediaTrackPrevious"), (KeyCode::MediaNext, "MediaTrackNext"),
+    (KeyCode::MediaSelect, "MediaSelect"), (KeyCode::MediaEject, "Eject"),
+    (KeyCode::VolumeMute, "AudioVolumeMute"), (KeyCode::VolumeUp, "AudioVolumeUp"),
+    (KeyCode::VolumeDown, "AudioVolumeDown"),
+    (KeyCode::AppMail, "LaunchMail"), (KeyCode::AppCalculator, "LaunchApp2"),
+    (KeyCode::BrowserSearch, "BrowserSearch"), (KeyCode::BrowserHome, "BrowserHome"),
+    (KeyCode::BrowserBack, "BrowserBack"), (KeyCode::BrowserForward, "BrowserForward"),
+    (KeyCode::BrowserStop, "BrowserStop"), (KeyCode::BrowserRefresh, "BrowserRefresh"),
+    (KeyCode::BrowserFavorites, "BrowserFavorites"),
+    (KeyCode::Katakana, "Lang3"), (KeyCode::Hiragana, "Lang4"), (KeyCode::Kanji, "Convert"),
+    (KeyCode::Underscore, "IntlRo"), (KeyCode::Yen, "IntlYen"),
+    (KeyCode::SunHelp, "Help"), (KeyCode::SunProps, "Props"), (KeyCode::SunOpen, "Open"),
+    (KeyCode::SunFind, "Find"), (KeyCode::SunAgain, "Again"), (KeyCode::SunUndo, "Undo"),
+    (KeyCode::SunCopy, "Copy"), (KeyCode::SunInsert, "Paste"), (KeyCode::SunCut, "Cut"),
+];
+
+// `key` values of keys that do not produce a character. When several keys
+// share a value, the first one listed is used when converting back.
+#[rustfmt::skip]
+const NAMED_KEYS: &[(KeyCode, &str)] = &[
+    (KeyCode::Escape, "Escape"),
+    (KeyCode::F1, "F1"), (KeyCode::F2, "F2"), (KeyCode::F3, "F3"), (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"), (KeyCode::F6, "F6"), (KeyCode::F7, "F7"), (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"), (KeyCode::F10, "F10"), (KeyCode::F11, "F11"), (KeyCode::F12, "F12"),
+    (KeyCode::F13, "F13"), (KeyCode::F14, "F14"), (KeyCode::F15, "F15"), (KeyCode::F16, "F16"),
+    (KeyCode::F17, "F17"), (KeyCode::F18, "F18"), (KeyCode::F19, "F19"), (KeyCode::F20, "F20"),
+    (KeyCode::F21, "F21"), (KeyCode::F22, "F22"), (KeyCode::F23, "F23"), (KeyCode::F24, "F24"),
+    (KeyCode::Backspace, "Backspace"), (KeyCode::Tab, "Tab"), (KeyCode::CapsLock, "CapsLock"),
+    (KeyCode::Enter, "Enter"), (KeyCode::PrintScreen, "PrintScreen"),
+    (KeyCode::ScrollLock, "ScrollLock"), (KeyCode::Pause, "Pause"),
+    (KeyCode::Insert, "Insert"), (KeyCode::Delete, "Delete"), (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"), (KeyCode::PageUp, "PageUp"), (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Up, "ArrowUp"), (KeyCode::Left, "ArrowLeft"), (KeyCode::Right, "ArrowRight"),
+    (KeyCode::Down, "ArrowDown"), (KeyCode::Clear, "Clear"), (KeyCode::NumLock, "NumLock"),
+    (KeyCode::KpEnter, "Enter"), (KeyCode::KpEnd, "End"), (KeyCode::KpDown, "ArrowDown"),
+    (KeyCode::KpPageDown, "PageDown"), (KeyCode::KpLeft, "ArrowLeft"), (KeyCode::KpClear, "Clear"),
+    (KeyCode::KpRight, "ArrowRight"), (KeyCode::KpHome, "Home"), (KeyCode::KpUp, "ArrowUp"),
+    (KeyCode::KpPageUp, "PageUp"), (KeyCode::KpInsert, "Insert"), (KeyCode::KpDelete, "Delete"),
+    (KeyCode::ShiftL, "Shift"), (KeyCode::ShiftR, "Shift"),
+    (KeyCode::ControlL, "Control"), (KeyCode::ControlR, "Control"),
+    (KeyCode::AltL, "Alt"), (KeyCode::AltR, "Alt"), (KeyCode::MetaL, "Meta"), (KeyCode::MetaR, "Meta"),
+    (KeyCode::ContextMenu, "ContextMenu"), (KeyCode::Power, "Power"), (KeyCode::Sleep, "Standby"),
+    (KeyCode::Wake, "WakeUp"),
+    (KeyCode::MediaPlay, "MediaPlayPause"), (KeyCode::MediaStop, "MediaStop"),
+    (KeyCode::MediaPrevious, "MediaTrackPrevious"), (KeyCode::MediaNext, "MediaTrackNext"),
+    (KeyCode::MediaSelect, "LaunchMediaPlayer"), (KeyCode::MediaEject, "Eject"),
+    (KeyCode::VolumeMute, "AudioVolumeMute"), (KeyCode::VolumeUp, "AudioVolumeUp"),
+    (KeyCode::VolumeDown, "AudioVolumeDown"),
+    (KeyCode::AppMail, "LaunchMail"), (KeyCode::AppCalculator, "LaunchCalculator"),
+    (KeyCode::AppMusic, "LaunchMusicPlayer"),
+    (KeyCode::BrowserSearch, "BrowserSearch"), (KeyCode::BrowserHome, "BrowserHome"),
+    (KeyCode::BrowserBack, "BrowserBack"), (KeyCode::BrowserForward, "BrowserForward"),
+    (KeyCode::BrowserStop, "BrowserStop"), (KeyCode::BrowserRefresh, "BrowserRefresh"),
+    (KeyCode::BrowserFavorites, "BrowserFavorites"),
+    (KeyCode::Katakana, "Katakana"), (KeyCode::Hiragana, "Hiragana"), (KeyCode::Kanji, "Convert"),
+    (KeyCode::SunHelp, "Help"), (KeyCode::SunStop, "Cancel"), (KeyCode::SunProps, "Props"),
+    (KeyCode::SunFind, "Find"), (KeyCode::SunAgain, "Again"), (KeyCode::SunUndo, "Undo"),
+    (KeyCode::SunCopy, "Copy"), (KeyCode::SunInsert, "Paste"), (KeyCode::SunCut, "Cut"),
+];
+
+/// Translates a key code to a W3C `code` string.
+///
+/// The keypad navigation keys libuiohook reports while Num Lock is off
+/// (`KpEnd`, `KpUp`, ...) translate to the code of the digit key they share,
+/// so [`from_code`] returns the digit key for them.
+///
+/// # Arguments
+///
+/// * `key` - The key to translate.
+///
+/// # Returns
+///
+/// The `code` string, or `None` if the key has no W3C code.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::w3c;
+///
+/// assert_eq!(w3c::to_code(KeyCode::ShiftL), Some("ShiftLeft"));
+/// assert_eq!(w3c::to_code(KeyCode::KpHome), Some("Numpad7"));
+/// ```
+pub fn to_code(key: KeyCode) -> Option<&'static str> {
+    let key = match key {
+        KeyCode::KpEnd => KeyCode::Kp1,
+        KeyCode::KpDown => KeyCode::Kp2,
+        KeyCode::KpPageDown => KeyCode::Kp3,
+        KeyCode::KpLeft => KeyCode::Kp4,
+        KeyCode::KpClear => KeyCode::Kp5,
+        KeyCode::KpRight => KeyCode::Kp6,
+        KeyCode::KpHome => KeyCode::Kp7,
+        KeyCode::KpUp => KeyCode::Kp8,
+        KeyCode::KpPageUp => KeyCode::Kp9,
+        KeyCode::KpInsert => KeyCode::Kp0,
+        KeyCode::KpDelete => KeyCode::KpSeparator,
+        key => key,
+    };
+    super::lookup(CODES, key)
+}
+
+/// Translates a W3C `code` string to a key code.
+///
+/// # Arguments
+///
+/// * `code` - The `code` string, e.g. `"KeyA"` or `"Numpad5"`.
+///
+/// # Returns
+///
+/// The key code, or `None` if the code is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::w3c;
+///
+/// assert_eq!(w3c::from_code("Numpad5"), Some(KeyCode::Kp5));
+/// assert_eq!(w3c::from_code("Unidentified"), None);
+/// ```
+pub fn from_code(code: &str) -> Option<KeyCode> {
+    super::reverse_lookup(CODES, code)
+}
+
+/// Computes the W3C `key` string for a key press.
+///
+/// A printable `key_char` is used as is. Otherwise non-printing keys get their
+/// named value (`"Shift"`, `"ArrowUp"`, `"Enter"`), and printing keys get the
+/// character they produce on a US layout given the Shift and Caps Lock state
+/// in `modifiers`.
+///
+/// # Arguments
+///
+/// * `key` - The key code of the event.
+/// * `key_char` - The character of the event, if known.
+/// * `modifiers` - The modifier state of the event.
+///
+/// # Returns
+///
+/// The `key` string, or [`UNIDENTIFIED`] if the key's meaning is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::hook::modifiers::Modifiers;
+/// use uiohook_rs::keymap::w3c;
+///
+/// assert_eq!(w3c::to_key(KeyCode::A, None, Modifiers::empty()), "a");
+/// assert_eq!(w3c::to_key(KeyCode::Num1, None, Modifiers::SHIFT_L), "!");
+/// assert_eq!(w3c::to_key(KeyCode::ShiftR, None, Modifiers::SHIFT_R), "Shift");
+/// assert_eq!(w3c::to_key(KeyCode::Undefined, Some('é'), Modifiers::empty()), "é");
+/// ```
+pub fn to_key(key: KeyCode, key_char: Option<char>, modifiers: Modifiers) -> String {
+    if let Some(c) = key_char.filter(|c| !c.is_control()) {
+        return c.to_string();
+    }
+    if let Some(name) = super::lookup(NAMED_KEYS, key) {
+        return name.to_string();
+    }
+    match printed_char(key, modifiers) {
+        Some(c) => c.to_string(),
+        None => UNIDENTIFIED.to_string(),
+    }
+}
+
+/// Translates a W3C `key` string to the key code that produces it.
+///
+/// Single characters are looked up on a US layout, so `"a"` and `"A"` both
+/// give `KeyCode::A`. Named values shared by several keys give the main one:
+/// `"Shift"` gives `KeyCode::ShiftL` and `"Enter"` gives `KeyCode::Enter`.
+///
+/// # Arguments
+///
+/// * `key` - The `key` string.
+///
+/// # Returns
+///
+/// The key code, or `None` if no key produces the value.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::w3c;
+///
+/// assert_eq!(w3c::from_key("?"), Some(KeyCode::Slash));
+/// assert_eq!(w3c::from_key("ArrowLeft"), Some(KeyCode::Left));
+/// ```
+pub fn from_key(key: &str) -> Option<KeyCode> {
+    match single_char(key) {
+        Some(c) => us_layout_key(c).map(|(key, _)| key),
+        None => super::reverse_lookup(NAMED_KEYS, key),
+    }
+}
+
+/// Builds a keyboard event from a browser `KeyboardEvent`'s `code` and `key`.
+///
+/// The key code is taken from `code`, falling back to `key` when the code is
+/// empty or unknown. A single-character `key` becomes the event's `key_char`.
+/// Typed events for characters that no key produces are given
+/// `KeyCode::Undefined`, matching what [`type_text`](crate::type_text) posts.
+///
+/// # Arguments
+///
+/// * `event_type` - The type of the event to build.
+/// * `code` - The W3C `code` string, or an empty string if unknown.
+/// * `key` - The W3C `key` string.
+/// * `modifiers` - The modifier state, built from the browser's `shiftKey`,
+///   `ctrlKey`, `altKey` and `metaKey` flags.
+///
+/// # Returns
+///
+/// The keyboard event, ready to be posted.
+///
+/// # Errors
+///
+/// Returns `UiohookError::InvalidArgument` if neither `code` nor `key`
+/// identifies a key for a press or release.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::{KeyCode, KeyboardEventType};
+/// use uiohook_rs::hook::modifiers::Modifiers;
+/// use uiohook_rs::keymap::w3c;
+///
+/// let event = w3c::to_keyboard_event(KeyboardEventType::Pressed, "KeyA", "A", Modifiers::SHIFT_L)
+///     .expect("Failed to convert event");
+/// assert_eq!(event.key_code, KeyCode::A);
+/// assert_eq!(event.key_char, Some('A'));
+/// ```
+pub fn to_keyboard_event(
+    event_type: KeyboardEventType,
+    code: &str,
+    key: &str,
+    modifiers: Modifiers,
+) -> Result<KeyboardEvent> {
+    let key_code = match from_code(code).or_else(|| from_key(key)) {
+        Some(key_code) => key_code,
+        None if event_type == KeyboardEventType::Typed && single_char(key).is_some() => KeyCode::Undefined,
+        None => return Err(UiohookError::InvalidArgument("unknown W3C key")),
+    };
+    Ok(KeyboardEvent {
+        event_type,
+        key_code,
+        raw_code: u32::from(key_code) as u16,
+        key_char: single_char(key),
+        meta: EventMeta::new(Default::default(), modifiers),
+    })
+}
+
+// The character a printing key produces on a US layout.
+fn printed_char(key: KeyCode, modifiers: Modifiers) -> Option<char> {
+    let keypad = match key {
+        KeyCode::KpDivide => Some('/'),
+        KeyCode::KpMultiply => Some('*'),
+        KeyCode::KpSubtract => Some('-'),
+        KeyCode::KpAdd => Some('+'),
+        KeyCode::KpEquals => Some('='),
+        KeyCode::KpSeparator => Some('.'),
+        KeyCode::KpComma => Some(','),
+        KeyCode::Kp0 => Some('0'),
+        KeyCode::Kp1 => Some('1'),
+        KeyCode::Kp2 => Some('2'),
+        KeyCode::Kp3 => Some('3'),
+        KeyCode::Kp4 => Some('4'),
+        KeyCode::Kp5 => Some('5'),
+        KeyCode::Kp6 => Some('6'),
+        KeyCode::Kp7 => Some('7'),
+        KeyCode::Kp8 => Some('8'),
+        KeyCode::Kp9 => Some('9'),
+        _ => None,
+    };
+    if keypad.is_some() {
+        return keypad;
+    }
+
+    let shift = modifiers.shift();
+    let c = (' '..='~').find(|&c| us_layout_key(c) == Some((key, shift)))?;
+    if c.is_ascii_alphabetic() && modifiers.caps_lock() {
+        // Caps Lock inverts the case Shift would give.
+        Some(if shift { c.to_ascii_lowercase() } else { c.to_ascii_uppercase() })
+    } else {
+        Some(c)
+    }
+}
+
+// The character `key` consists of, if it is exactly one character long.
+fn single_char(key: &str) -> Option<char> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_table_is_bijective() {
+        super::super::assert_bijective(CODES);
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        // Keys the W3C code values have no entry for.
+        const NO_CODE: [KeyCode; 8] = [
+            KeyCode::Clear, KeyCode::AppMusic, KeyCode::AppPictures, KeyCode::Furigana,
+            KeyCode::SunStop, KeyCode::SunFront, KeyCode::Undefined, KeyCode::CharUndefined,
+        ];
+
+        for key in KeyCode::all() {
+            match to_code(key) {
+                Some(code) => {
+                    assert!(!NO_CODE.contains(&key), "{:?} has a code, {:?}", key, code);
+                    let back = from_code(code).unwrap();
+                    assert!(back == key || (key.is_keypad() && back.is_keypad()), "{:?} came back as {:?}", key, back);
+                }
+                None => assert!(NO_CODE.contains(&key), "{:?} has no code", key),
+            }
+        }
+        assert_eq!(to_code(KeyCode::KpDelete), Some("NumpadDecimal"));
+        assert_eq!(to_code(KeyCode::Other(0x04)), None);
+    }
+
+    #[test]
+    fn test_to_key() {
+        let none = Modifiers::empty();
+        assert_eq!(to_key(KeyCode::A, None, none), "a");
+        assert_eq!(to_key(KeyCode::A, None, Modifiers::SHIFT_R), "A");
+        assert_eq!(to_key(KeyCode::A, None, Modifiers::CAPS_LOCK), "A");
+        assert_eq!(to_key(KeyCode::A, None, Modifiers::CAPS_LOCK | Modifiers::SHIFT_L), "a");
+        assert_eq!(to_key(KeyCode::Num2, None, Modifiers::SHIFT_L), "@");
+        assert_eq!(to_key(KeyCode::Num2, None, Modifiers::CAPS_LOCK), "2");
+        assert_eq!(to_key(KeyCode::Space, None, none), " ");
+        assert_eq!(to_key(KeyCode::Kp5, None, Modifiers::SHIFT_L), "5");
+        assert_eq!(to_key(KeyCode::KpHome, None, none), "Home");
+        assert_eq!(to_key(KeyCode::Enter, Some('\r'), none), "Enter");
+        assert_eq!(to_key(KeyCode::MetaL, None, none), "Meta");
+        assert_eq!(to_key(KeyCode::Other(0xE0F0), None, none), UNIDENTIFIED);
+        assert_eq!(to_key(KeyCode::Undefined, Some('€'), none), "€");
+    }
+
+    #[test]
+    fn test_from_key() {
+        assert_eq!(from_key("a"), Some(KeyCode::A));
+        assert_eq!(from_key("A"), Some(KeyCode::A));
+        assert_eq!(from_key("!"), Some(KeyCode::Num1));
+        assert_eq!(from_key(" "), Some(KeyCode::Space));
+        assert_eq!(from_key("Shift"), Some(KeyCode::ShiftL));
+        assert_eq!(from_key("Enter"), Some(KeyCode::Enter));
+        assert_eq!(from_key("ArrowDown"), Some(KeyCode::Down));
+        assert_eq!(from_key("é"), None);
+        assert_eq!(from_key(UNIDENTIFIED), None);
+        assert_eq!(from_key(""), None);
+    }
+
+    #[test]
+    fn test_printable_keys_round_trip() {
+        for c in ' '..='~' {
+            let (key, shift) = us_layout_key(c).unwrap();
+            let modifiers = if shift { Modifiers::SHIFT_L } else { Modifiers::empty() };
+            let code = to_code(key).unwrap();
+            let value = to_key(key, None, modifiers);
+            assert_eq!(value, c.to_string());
+
+            let event = to_keyboard_event(KeyboardEventType::Pressed, code, &value, modifiers).unwrap();
+            assert_eq!(event.key_code, key);
+            assert_eq!(event.key_char, Some(c));
+            assert_eq!(event.meta.modifiers, modifiers);
+        }
+    }
+
+    #[test]
+    fn test_to_keyboard_event() {
+        let none = Modifiers::empty();
+
+        let event = to_keyboard_event(KeyboardEventType::Released, "ShiftRight", "Shift", none).unwrap();
+        assert_eq!(event.key_code, KeyCode::ShiftR);
+        assert_eq!(event.key_char, None);
+
+        // Without a code, the key value decides.
+        let event = to_keyboard_event(KeyboardEventType::Pressed, "", "PageUp", none).unwrap();
+        assert_eq!(event.key_code, KeyCode::PageUp);
+
+        let event = to_keyboard_event(KeyboardEventType::Typed, "", "ß", none).unwrap();
+        assert_eq!(event.key_code, KeyCode::Undefined);
+        assert_eq!(event.key_char, Some('ß'));
+
+        assert_eq!(
+            to_keyboard_event(KeyboardEventType::Pressed, "", "ß", none).err(),
+            Some(UiohookError::InvalidArgument("unknown W3C key"))
+        );
+        assert!(to_keyboard_event(KeyboardEventType::Pressed, "Fn", UNIDENTIFIED, none).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! X11 keysyms.
+//!
+//! Keysyms are the `XK_*` values from `X11/keysymdef.h` and the vendor
+//! `XF86XK_*`/`SunXK_*` ranges. Letters translate to their unshifted
+//! (lowercase) keysym; [`from_keysym`] also accepts the uppercase form.
+//!
+//! The 102nd key ([`KeyCode::LesserGreater`]) and the JIS Ro key
+//! ([`KeyCode::Underscore`]) have no keysym of their own: `less` and
+//! `underscore` are the shifted Comma and Minus on a US layout, so they are
+//! left out rather than shadowing those keys.
+
+use crate::hook::keyboard::KeyCode;
+
+#[rustfmt::skip]
+const TABLE: &[(KeyCode, u32)] = &[
+    (KeyCode::Escape, 0xFF1B),
+    (KeyCode::F1, 0xFFBE), (KeyCode::F2, 0xFFBF), (KeyCode::F3, 0xFFC0), (KeyCode::F4, 0xFFC1),
+    (KeyCode::F5, 0xFFC2), (KeyCode::F6, 0xFFC3), (KeyCode::F7, 0xFFC4), (KeyCode::F8, 0xFFC5),
+    (KeyCode::F9, 0xFFC6), (KeyCode::F10, 0xFFC7), (KeyCode::F11, 0xFFC8), (KeyCode::F12, 0xFFC9),
+    (KeyCode::F13, 0xFFCA), (KeyCode::F14, 0xFFCB), (KeyCode::F15, 0xFFCC), (KeyCode::F16, 0xFFCD),
+    (KeyCode::F17, 0xFFCE), (KeyCode::F18, 0xFFCF), (KeyCode::F19, 0xFFD0), (KeyCode::F20, 0xFFD1),
+    (KeyCode::F21, 0xFFD2), (KeyCode::F22, 0xFFD3), (KeyCode::F23, 0xFFD4), (KeyCode::F24, 0xFFD5),
+    (KeyCode::Backquote, 0x60),
+    (KeyCode::Num1, 0x31), (KeyCode::Num2, 0x32), (KeyCode::Num3, 0x33), (KeyCode::Num4, 0x34),
+    (KeyCode::Num5, 0x35), (KeyCode::Num6, 0x36), (KeyCode::Num7, 0x37), (KeyCode::Num8, 0x38),
+    (KeyCode::Num9, 0x39), (KeyCode::Num0, 0x30),
+    (KeyCode::Minus, 0x2D), (KeyCode::Equals, 0x3D), (KeyCode::Backspace, 0xFF08),
+    (KeyCode::Tab, 0xFF09), (KeyCode::CapsLock, 0xFFE5),
+    (KeyCode::A, 0x61), (KeyCode::B, 0x62), (KeyCode::C, 0x63), (KeyCode::D, 0x64),
+    (KeyCode::E, 0x65), (KeyCode::F, 0x66), (KeyCode::G, 0x67), (KeyCode::H, 0x68),
+    (KeyCode::I, 0x69), (KeyCode::J, 0x6A), (KeyCode::K, 0x6B), (KeyCode::L, 0x6C),
+    (KeyCode::M, 0x6D), (KeyCode::N, 0x6E), (KeyCode::O, 0x6F), (KeyCode::P, 0x70),
+    (KeyCode::Q, 0x71), (KeyCode::R, 0x72), (KeyCode::S, 0x73), (KeyCode::T, 0x74),
+    (KeyCode::U, 0x75), (KeyCode::V, 0x76), (KeyCode::W, 0x77), (KeyCode::X, 0x78),
+    (KeyCode::Y, 0x79), (KeyCode::Z, 0x7A),
+    (KeyCode::OpenBracket, 0x5B), (KeyCode::CloseBracket, 0x5D), (KeyCode::Backslash, 0x5C),
+    (KeyCode::Semicolon, 0x3B), (KeyCode::Quote, 0x27), (KeyCode::Enter, 0xFF0D),
+    (KeyCode::Comma, 0x2C), (KeyCode::Period, 0x2E), (KeyCode::Slash, 0x2F), (KeyCode::Space, 0x20),
+    (KeyCode::PrintScreen, 0xFF61), (KeyCode::ScrollLock, 0xFF14), (KeyCode::Pause, 0xFF13),
+    (KeyCode::Insert, 0xFF63), (KeyCode::Delete, 0xFFFF), (KeyCode::Home, 0xFF50),
+    (KeyCode::End, 0xFF57), (KeyCode::PageUp, 0xFF55), (KeyCode::PageDown, 0xFF56),
+    (KeyCode::Up, 0xFF52), (KeyCode::Left, 0xFF51), (KeyCode::Clear, 0xFF0B),
+    (KeyCode::Right, 0xFF53), (KeyCode::Down, 0xFF54),
+    (KeyCode::NumLock, 0xFF7F), (KeyCode::KpDivide, 0xFFAF), (KeyCode::KpMultiply, 0xFFAA),
+    (KeyCode::KpSubtract, 0xFFAD), (KeyCode::KpEquals, 0xFFBD), (KeyCode::KpAdd, 0xFFAB),
+    (KeyCode::KpEnter, 0xFF8D), (KeyCode::KpSeparator, 0xFFAE),
+    (KeyCode::Kp1, 0xFFB1), (KeyCode::Kp2, 0xFFB2), (KeyCode::Kp3, 0xFFB3), (KeyCode::Kp4, 0xFFB4),
+    (KeyCode::Kp5, 0xFFB5), (KeyCode::Kp6, 0xFFB6), (KeyCode::Kp7, 0xFFB7), (KeyCode::Kp8, 0xFFB8),
+    (KeyCode::Kp9, 0xFFB9), (KeyCode::Kp0, 0xFFB0),
+    (KeyCode::KpEnd, 0xFF9C), (KeyCode::KpDown, 0xFF99), (KeyCode::KpPageDown, 0xFF9B),
+    (KeyCode::KpLeft, 0xFF96), (KeyCode::KpClear, 0xFF9D), (KeyCode::KpRight, 0xFF98),
+    (KeyCode::KpHome, 0xFF95), (KeyCode::KpUp, 0xFF97), (KeyCode::KpPageUp, 0xFF9A),
+    (KeyCode::KpInsert, 0xFF9E), (KeyCode::KpDelete, 0xFF9F),
+    (KeyCode::ShiftL, 0xFFE1), (KeyCode::ShiftR, 0xFFE2), (KeyCode::ControlL, 0xFFE3),
+    (KeyCode::ControlR, 0xFFE4), (KeyCode::AltL, 0xFFE9), (KeyCode::AltR, 0xFFEA),
+    (KeyCode::MetaL, 0xFFEB), (KeyCode::MetaR, 0xFFEC), (KeyCode::ContextMenu, 0xFF67),
+    (KeyCode::Power, 0x1008_FF2A), (KeyCode::Sleep, 0x1008_FF2F), (KeyCode::Wake, 0x1008_FF2B),
+    (KeyCode::MediaPlay, 0x1008_FF14), (KeyCode::MediaStop, 0x1008_FF15),
+    (KeyCode::MediaPrevious, 0x1008_FF16), (KeyCode::MediaNext, 0x1008_FF17),
+    (KeyCode::MediaSelect, 0x1008_FF32), (KeyCode::MediaEject, 0x1008_FF2C),
+    (KeyCode::VolumeMute, 0x1008_FF12), (KeyCode::VolumeUp, 0x1008_FF13),
+    (KeyCode::VolumeDown, 0x1008_FF11),
+    (KeyCode::AppMail, 0x1008_FF19), (KeyCode::AppCalculator, 0x1008_FF1D),
+    (KeyCode::AppMusic, 0x1008_FF92), (KeyCode::AppPictures, 0x1008_FF91),
+    (KeyCode::BrowserSearch, 0x1008_FF1B), (KeyCode::BrowserHome, 0x1008_FF18),
+    (KeyCode::BrowserBack, 0x1008_FF26), (KeyCode::BrowserForward, 0x1008_FF27),
+    (KeyCode::BrowserStop, 0x1008_FF28), (KeyCode::BrowserRefresh, 0x1008_FF29),
+    (KeyCode::BrowserFavorites, 0x1008_FF30),
+    (KeyCode::Katakana, 0xFF26), (KeyCode::Kanji, 0xFF23),
+    (KeyCode::Hiragana, 0xFF25), (KeyCode::Yen, 0xA5), (KeyCode::KpComma, 0xFFAC),
+    (KeyCode::SunHelp, 0xFF6A), (KeyCode::SunStop, 0xFF69), (KeyCode::SunProps, 0x1005_FF70),
+    (KeyCode::SunFront, 0x1005_FF71), (KeyCode::SunOpen, 0x1005_FF73), (KeyCode::SunFind, 0xFF68),
+    (KeyCode::SunAgain, 0xFF66), (KeyCode::SunUndo, 0xFF65), (KeyCode::SunCopy, 0x1008_FF57),
+    (KeyCode::SunInsert, 0x1008_FF6D), (KeyCode::SunCut, 0x1008_FF58),
+];
+
+/// Translates a key code to an X11 keysym.
+///
+/// # Arguments
+///
+/// * `key` - The key to translate.
+///
+/// # Returns
+///
+/// The keysym the key produces without modifiers, or `None` if the key has no
+/// keysym.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::x11;
+///
+/// // XK_a
+/// assert_eq!(x11::to_keysym(KeyCode::A), Some(0x61));
+/// ```
+pub fn to_keysym(key: KeyCode) -> Option<u32> {
+    super::lookup(TABLE, key)
+}
+
+/// Translates an X11 keysym to a key code.
+///
+/// # Arguments
+///
+/// * `keysym` - The keysym. Uppercase Latin letters are accepted as well as
+///   lowercase ones.
+///
+/// # Returns
+///
+/// The key code, or `None` if the keysym has no `KeyCode` equivalent.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::hook::keyboard::KeyCode;
+/// use uiohook_rs::keymap::x11;
+///
+/// assert_eq!(x11::from_keysym(0x61), Some(KeyCode::A));
+/// assert_eq!(x11::from_keysym(0x41), Some(KeyCode::A));
+/// ```
+pub fn from_keysym(keysym: u32) -> Option<KeyCode> {
+    let keysym = match keysym {
+        // XK_A..XK_Z
+        0x41..=0x5A => keysym + 0x20,
+        _ => keysym,
+    };
+    super::reverse_lookup(TABLE, keysym)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_bijective() {
+        super::super::assert_bijective(TABLE);
+    }
+
+    #[test]
+    fn test_known_keysyms() {
+        assert_eq!(to_keysym(KeyCode::Escape), Some(0xFF1B));
+        assert_eq!(to_keysym(KeyCode::PageUp), Some(0xFF55));
+        assert_eq!(to_keysym(KeyCode::VolumeUp), Some(0x1008_FF13));
+        assert_eq!(from_keysym(0xFFEB), Some(KeyCode::MetaL));
+        assert_eq!(from_keysym(0x5A), Some(KeyCode::Z));
+        assert_eq!(from_keysym(0x5B), Some(KeyCode::OpenBracket));
+        assert_eq!(from_keysym(0), None);
+        // Henkan_Mode, which xkb assigns to the JIS Henkan key.
+        assert_eq!(to_keysym(KeyCode::Kanji), Some(0xFF23));
+        assert_eq!(to_keysym(KeyCode::Furigana), None);
+        assert_eq!(to_keysym(KeyCode::LesserGreater), None);
+        assert_eq!(from_keysym(0x3C), None);
+        assert_eq!(from_keysym(0x5F), None);
+    }
+}
@@ -38,16 +38,34 @@
 #![allow(missing_docs)]
 
 mod bindings;
+pub mod click;
+pub mod gesture;
 pub mod hook;
+pub mod hotkey;
+pub mod input_state;
+pub mod keymap;
 pub mod error;
+pub mod logger;
+pub mod sequence;
 pub mod utils;
 // pub mod legacy;
 
 // Re-export the main components
-pub use hook::{Uiohook, EventHandler, UiohookEvent};
-pub use hook::keyboard::{KeyboardEvent, KeyboardEventType, key_tap, key_toggle};
+pub use hook::{Uiohook, EventHandler, EventFilter, EventVerdict, EventMeta, UiohookEvent};
+pub use hook::backend::{HookBackend, LibUiohookBackend, MockBackend};
+pub use hook::channel::{EventReceiver, OverflowPolicy};
+#[cfg(feature = "async")]
+pub use hook::stream::EventStream;
+pub use hook::dispatch::{subscribe, subscribe_filter, unsubscribe, Dispatcher, PanicPolicy, SubscriptionId};
+pub use hook::modifiers::Modifiers;
+pub use hook::keyboard::{KeyboardEvent, KeyboardEventType, key_tap, key_toggle, type_text};
 pub use hook::mouse::{MouseEvent, MouseEventType};
-pub use hook::wheel::WheelEvent;
+pub use hook::wheel::{WheelDirection, WheelEvent, WheelScrollType};
+pub use hotkey::{Hotkey, HotkeyId, HotkeyManager, HotkeyTrigger};
+pub use sequence::{SequenceId, SequenceMatcher};
+pub use gesture::{GestureEvent, GestureRecognizer};
+pub use click::{Click, ClickLayer, ClickTracker};
+pub use input_state::InputState;
 pub use error::UiohookError;
 
 // Re-export utility functions
@@ -59,6 +77,9 @@ pub use utils::{
     get_pointer_acceleration_threshold,
     get_pointer_sensitivity,
     get_multi_click_time,
+    system_properties,
+    SystemProperties,
+    DEFAULT_MULTI_CLICK_TIME,
 };
 
 // Re-export constants from bindings
@@ -119,6 +140,8 @@ pub use bindings::{
     MASK_SHIFT_L, MASK_CTRL_L, MASK_META_L, MASK_ALT_L,
     MASK_SHIFT_R, MASK_CTRL_R, MASK_META_R, MASK_ALT_R,
     MASK_SHIFT, MASK_CTRL, MASK_META, MASK_ALT,
+    MASK_BUTTON1, MASK_BUTTON2, MASK_BUTTON3, MASK_BUTTON4, MASK_BUTTON5,
+    MASK_NUM_LOCK, MASK_CAPS_LOCK, MASK_SCROLL_LOCK,
 };
 
 /// Version of the crate
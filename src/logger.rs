@@ -0,0 +1,212 @@
+//! Forwarding of libuiohook's diagnostics to the `log` ecosystem.
+//!
+//! libuiohook reports what it is doing through a logger procedure. This module
+//! installs one that forwards each message to the [`log`] crate, or to
+//! [`tracing`](https://docs.rs/tracing) when the `tracing` feature is enabled,
+//! using the `uiohook` target. The bridge is installed automatically when the
+//! hook runs; messages more verbose than [`level`] are discarded.
+
+use crate::bindings;
+use log::{Level, LevelFilter};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_uint};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+/// The target used for every forwarded message.
+pub const TARGET: &str = "uiohook";
+
+// libuiohook logs every captured event at debug level, so stay quiet by default.
+static MIN_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+
+static INSTALL_LOGGER: Once = Once::new();
+
+extern "C" {
+    fn uiohook_rs_install_logger();
+}
+
+/// Install the bridge as libuiohook's logger procedure.
+///
+/// Called automatically before the hook runs; call it yourself to also see
+/// messages from functions such as [`create_screen_info`](crate::create_screen_info)
+/// used before that. Installing more than once has no effect.
+pub fn install() {
+    INSTALL_LOGGER.call_once(|| unsafe {
+        uiohook_rs_install_logger();
+    });
+}
+
+/// Set the minimum level of libuiohook messages that are forwarded.
+///
+/// Defaults to `LevelFilter::Info`. The `log` or `tracing` subscriber may
+/// filter further.
+///
+/// # Arguments
+///
+/// * `level` - The most verbose level to forward; `LevelFilter::Off` silences libuiohook.
+///
+/// # Examples
+///
+/// ```
+/// use log::LevelFilter;
+/// use uiohook_rs::logger;
+///
+/// logger::set_level(LevelFilter::Debug);
+/// assert_eq!(logger::level(), LevelFilter::Debug);
+/// ```
+pub fn set_level(level: LevelFilter) {
+    MIN_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+/// Returns the minimum level of libuiohook messages that are forwarded.
+pub fn level() -> LevelFilter {
+    match MIN_LEVEL.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+fn level_from_raw(level: c_uint) -> Level {
+    use bindings::log_level::*;
+    match level {
+        l if l == LOG_LEVEL_ERROR as c_uint => Level::Error,
+        l if l == LOG_LEVEL_WARN as c_uint => Level::Warn,
+        l if l == LOG_LEVEL_INFO as c_uint => Level::Info,
+        _ => Level::Debug,
+    }
+}
+
+fn forward(level: Level, message: &str) {
+    if level > self::level() {
+        return;
+    }
+    // libuiohook terminates its messages with a newline.
+    let message = message.trim_end();
+
+    #[cfg(feature = "tracing")]
+    match level {
+        Level::Error => tracing::error!(target: TARGET, "{}", message),
+        Level::Warn => tracing::warn!(target: TARGET, "{}", message),
+        Level::Info => tracing::info!(target: TARGET, "{}", message),
+        Level::Debug => tracing::debug!(target: TARGET, "{}", message),
+        Level::Trace => tracing::trace!(target: TARGET, "{}", message),
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    log::log!(target: TARGET, level, "{}", message);
+}
+
+// Called by `uiohook_rs_logger_proc` in logger_bridge.c with the formatted message.
+#[no_mangle]
+extern "C" fn uiohook_rs_log(level: c_uint, message: *const c_char) {
+    if message.is_null() {
+        return;
+    }
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    // A panicking `log` implementation must not unwind into libuiohook.
+    let _ = std::panic::catch_unwind(|| forward(level_from_raw(level), &message));
+}
+
+#[cfg(all(test, not(feature = "tracing")))]
+mod tests {
+    use super::*;
+    use log::{Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    static RECORDS: Mutex<Vec<(Level, String, String)>> = Mutex::new(Vec::new());
+
+    struct Capture;
+
+    impl Log for Capture {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            RECORDS.lock().unwrap().push((
+                record.level(),
+                record.target().to_string(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    extern "C" {
+        fn uiohook_rs_logger_proc(level: c_uint, format: *const c_char, ...) -> bool;
+    }
+
+    fn captured(needle: &str) -> Vec<(Level, String, String)> {
+        RECORDS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, _, message)| message.contains(needle))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_bridge_formats_and_filters() {
+        let _ = log::set_logger(&Capture);
+        log::set_max_level(LevelFilter::Trace);
+
+        let format = c"%s [%u]: hook %d started\n";
+        let function = c"hook_run";
+        unsafe {
+            uiohook_rs_logger_proc(
+                bindings::log_level::LOG_LEVEL_WARN as c_uint,
+                format.as_ptr(),
+                function.as_ptr(),
+                42 as c_uint,
+                7 as std::os::raw::c_int,
+            );
+            // Filtered out by the default minimum level.
+            uiohook_rs_logger_proc(
+                bindings::log_level::LOG_LEVEL_DEBUG as c_uint,
+                format.as_ptr(),
+                function.as_ptr(),
+                43 as c_uint,
+                7 as std::os::raw::c_int,
+            );
+        }
+
+        assert_eq!(
+            captured("hook_run [4"),
+            vec![(Level::Warn, TARGET.to_string(), "hook_run [42]: hook 7 started".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_long_messages_are_not_truncated() {
+        let _ = log::set_logger(&Capture);
+        log::set_max_level(LevelFilter::Trace);
+
+        let long = format!("long-{}\0", "x".repeat(2000));
+        unsafe {
+            uiohook_rs_logger_proc(
+                bindings::log_level::LOG_LEVEL_ERROR as c_uint,
+                c"%s".as_ptr(),
+                long.as_ptr() as *const c_char,
+            );
+        }
+
+        let records = captured("long-");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].2.len(), long.len() - 1);
+    }
+
+    #[test]
+    fn test_level_mapping() {
+        use bindings::log_level::*;
+        assert_eq!(level_from_raw(LOG_LEVEL_DEBUG as c_uint), Level::Debug);
+        assert_eq!(level_from_raw(LOG_LEVEL_INFO as c_uint), Level::Info);
+        assert_eq!(level_from_raw(LOG_LEVEL_WARN as c_uint), Level::Warn);
+        assert_eq!(level_from_raw(LOG_LEVEL_ERROR as c_uint), Level::Error);
+    }
+}
@@ -0,0 +1,51 @@
+// Formats libuiohook's printf-style log messages and hands them to Rust.
+//
+// libuiohook's logger_t is variadic, which Rust cannot implement, so this shim
+// is installed as the logger procedure instead and forwards the formatted
+// message to uiohook_rs_log (src/logger.rs).
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <uiohook.h>
+
+extern void uiohook_rs_log(unsigned int level, const char *message);
+
+bool uiohook_rs_logger_proc(unsigned int level, const char *format, ...) {
+    char buffer[512];
+    char *message = buffer;
+    va_list args, retry;
+
+    va_start(args, format);
+    va_copy(retry, args);
+    int length = vsnprintf(buffer, sizeof(buffer), format, args);
+    va_end(args);
+
+    if (length < 0) {
+        va_end(retry);
+        return false;
+    }
+
+    if ((size_t) length >= sizeof(buffer)) {
+        message = malloc((size_t) length + 1);
+        if (message == NULL) {
+            va_end(retry);
+            return false;
+        }
+        vsnprintf(message, (size_t) length + 1, format, retry);
+    }
+    va_end(retry);
+
+    uiohook_rs_log(level, message);
+
+    if (message != buffer) {
+        free(message);
+    }
+
+    return true;
+}
+
+void uiohook_rs_install_logger(void) {
+    hook_set_logger_proc(&uiohook_rs_logger_proc);
+}
@@ -0,0 +1,682 @@
+//! Multi-stroke key sequences such as `"Ctrl+X Ctrl+S"` or `"leader g d"`.
+//!
+//! A [`SequenceMatcher`] stores its bindings in a trie of strokes, where each
+//! stroke is a [`Hotkey`] chord. It is fed [`KeyboardEvent`]s, either directly
+//! through [`SequenceMatcher::feed`] or by subscribing it as an
+//! [`EventHandler`], and fires a binding once its last stroke is pressed.
+//!
+//! Strokes must follow each other within the matcher's timeout, measured with
+//! the events' own timestamps so replayed or injected input behaves the same
+//! as live input. Pressing Escape part way through cancels the sequence.
+//! Presses of modifier keys on their own never count as strokes, so Ctrl may
+//! stay held down across `Ctrl+X Ctrl+S`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use uiohook_rs::sequence::SequenceMatcher;
+//! use uiohook_rs::Uiohook;
+//!
+//! let sequences = SequenceMatcher::new();
+//! sequences.set_leader("Space").expect("Failed to set leader");
+//! sequences.bind("Ctrl+X Ctrl+S", |_| println!("Save")).expect("Failed to bind");
+//! sequences.bind("leader g d", |_| println!("Go to definition")).expect("Failed to bind");
+//! sequences.on_partial_match(|partial| println!("Waiting after {:?}", partial.strokes));
+//!
+//! let uiohook = Uiohook::new(sequences.clone());
+//! uiohook.run().expect("Failed to run uiohook");
+//! ```
+
+use crate::error::{Result, UiohookError};
+use crate::hook::keyboard::{KeyCode, KeyboardEvent, KeyboardEventType};
+use crate::hotkey::Hotkey;
+use crate::{EventHandler, UiohookEvent};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// The default time allowed between two strokes of a sequence.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The key that cancels a partially typed sequence.
+pub const CANCEL_KEY: KeyCode = KeyCode::Escape;
+
+/// Identifies a sequence bound in a [`SequenceMatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SequenceId(u64);
+
+/// The state of a partially typed sequence, reported to show hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialMatch {
+    /// The strokes typed so far.
+    pub strokes: Vec<Hotkey>,
+    /// The strokes that can follow, in binding order.
+    pub next: Vec<Hotkey>,
+}
+
+/// Why a partially typed sequence was abandoned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CancelReason {
+    /// The cancel key ([`CANCEL_KEY`]) was pressed.
+    Escape,
+    /// The next stroke did not arrive within the timeout.
+    Timeout,
+    /// A stroke that continues no bound sequence was pressed.
+    Mismatch,
+}
+
+type SequenceCallback = Arc<dyn Fn(&KeyboardEvent) + Send + Sync>;
+type PartialCallback = Arc<dyn Fn(&PartialMatch) + Send + Sync>;
+type CancelCallback = Arc<dyn Fn(CancelReason) + Send + Sync>;
+
+#[derive(Default)]
+struct Node {
+    children: Vec<(Hotkey, Node)>,
+    binding: Option<(SequenceId, SequenceCallback)>,
+}
+
+impl Node {
+    fn child(&self, stroke: &Hotkey) -> Option<&Node> {
+        self.children.iter().find(|(s, _)| s == stroke).map(|(_, node)| node)
+    }
+
+    // The child whose stroke is pressed by `event`.
+    fn matching_child(&self, event: &KeyboardEvent) -> Option<(Hotkey, &Node)> {
+        self.children
+            .iter()
+            .find(|(stroke, _)| stroke.matches(event.key_code, event.meta.modifiers))
+            .map(|(stroke, node)| (*stroke, node))
+    }
+
+    // Removes the binding `id` below this node, pruning branches left empty.
+    fn remove(&mut self, id: SequenceId) -> bool {
+        if matches!(self.binding, Some((bid, _)) if bid == id) {
+            self.binding = None;
+            return true;
+        }
+        let Some(index) = self.children.iter_mut().position(|(_, node)| node.remove(id)) else {
+            return false;
+        };
+        let node = &self.children[index].1;
+        if node.binding.is_none() && node.children.is_empty() {
+            self.children.remove(index);
+        }
+        true
+    }
+}
+
+struct MatcherState {
+    root: Node,
+    next_id: u64,
+    timeout: Duration,
+    leader: Option<Hotkey>,
+    // Strokes of the sequence typed so far, and when the last one was pressed.
+    pending: Vec<Hotkey>,
+    last_stroke: Duration,
+    // Keys currently held down, to recognise auto-repeated presses.
+    held: HashSet<KeyCode>,
+    on_partial: Option<PartialCallback>,
+    on_cancel: Option<CancelCallback>,
+}
+
+impl MatcherState {
+    fn node(&self, path: &[Hotkey]) -> Option<&Node> {
+        path.iter().try_fold(&self.root, |node, stroke| node.child(stroke))
+    }
+
+    fn parse(&self, sequence: &str) -> Result<Vec<Hotkey>> {
+        sequence
+            .split_whitespace()
+            .map(|stroke| {
+                if stroke.eq_ignore_ascii_case("leader") {
+                    self.leader.ok_or(UiohookError::InvalidArgument("no leader key set"))
+                } else {
+                    stroke.parse()
+                }
+            })
+            .collect()
+    }
+}
+
+// Callbacks to run once the state lock is released.
+enum Notification {
+    Fired(SequenceCallback),
+    Partial(PartialCallback, PartialMatch),
+    Cancelled(CancelCallback, CancelReason),
+}
+
+/// Matches multi-stroke key sequences against keyboard events.
+///
+/// Clones share the same bindings and state. Callbacks run on the thread that
+/// feeds the events, without the matcher locked, so they may bind and unbind
+/// sequences.
+#[derive(Clone)]
+pub struct SequenceMatcher {
+    state: Arc<Mutex<MatcherState>>,
+}
+
+impl SequenceMatcher {
+    /// Create a matcher with no bindings, no leader key and [`DEFAULT_TIMEOUT`].
+    pub fn new() -> Self {
+        SequenceMatcher {
+            state: Arc::new(Mutex::new(MatcherState {
+                root: Node::default(),
+                next_id: 0,
+                timeout: DEFAULT_TIMEOUT,
+                leader: None,
+                pending: Vec::new(),
+                last_stroke: Duration::ZERO,
+                held: HashSet::new(),
+                on_partial: None,
+                on_cancel: None,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MatcherState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set the longest time allowed between two strokes of a sequence.
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.lock().timeout = timeout;
+    }
+
+    /// Returns the longest time allowed between two strokes of a sequence.
+    pub fn timeout(&self) -> Duration {
+        self.lock().timeout
+    }
+
+    /// Set the stroke that `leader` stands for in sequences bound afterwards.
+    ///
+    /// Sequences already bound keep the leader they were bound with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if the accelerator cannot be parsed.
+    pub fn set_leader(&self, accelerator: &str) -> Result<()> {
+        self.lock().leader = Some(accelerator.parse()?);
+        Ok(())
+    }
+
+    /// Bind a sequence of space-separated strokes to a callback.
+    ///
+    /// Each stroke is parsed as a [`Hotkey`], so keys whose names contain
+    /// spaces must be written without them (`"PageUp"`). The word `leader`
+    /// stands for the stroke set with [`SequenceMatcher::set_leader`].
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The strokes, e.g. `"Ctrl+X Ctrl+S"` or `"leader g d"`.
+    /// * `callback` - Called with the press that completed the sequence.
+    ///
+    /// # Returns
+    ///
+    /// The id to pass to [`SequenceMatcher::unbind`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if the sequence cannot be
+    /// parsed, uses `leader` before a leader key is set, or conflicts with a
+    /// bound sequence (see [`SequenceMatcher::bind_strokes`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::sequence::SequenceMatcher;
+    ///
+    /// let sequences = SequenceMatcher::new();
+    /// sequences.bind("Ctrl+X Ctrl+S", |_| println!("Save")).expect("Failed to bind");
+    ///
+    /// // "Ctrl+X" alone would make "Ctrl+X Ctrl+S" unreachable.
+    /// assert!(sequences.bind("Ctrl+X", |_| {}).is_err());
+    /// assert!(sequences.bind("leader x", |_| {}).is_err());
+    /// ```
+    pub fn bind<F>(&self, sequence: &str, callback: F) -> Result<SequenceId>
+    where
+        F: Fn(&KeyboardEvent) + Send + Sync + 'static,
+    {
+        let strokes = self.lock().parse(sequence)?;
+        self.bind_strokes(&strokes, callback)
+    }
+
+    /// Bind a sequence of strokes to a callback.
+    ///
+    /// A sequence conflicts with a bound one if either is a prefix of the
+    /// other, since the shorter one would always fire first. Strokes are
+    /// compared as written, so `Ctrl+X` and `LeftCtrl+X` do not conflict even
+    /// though both match a press of X with the left Control key held; the one
+    /// bound first wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `strokes` - The strokes of the sequence, in order.
+    /// * `callback` - Called with the press that completed the sequence.
+    ///
+    /// # Returns
+    ///
+    /// The id to pass to [`SequenceMatcher::unbind`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `UiohookError::InvalidArgument` if `strokes` is empty or the
+    /// sequence conflicts with a bound one.
+    pub fn bind_strokes<F>(&self, strokes: &[Hotkey], callback: F) -> Result<SequenceId>
+    where
+        F: Fn(&KeyboardEvent) + Send + Sync + 'static,
+    {
+        if strokes.is_empty() {
+            return Err(UiohookError::InvalidArgument("sequence has no strokes"));
+        }
+        let conflict = UiohookError::InvalidArgument("sequence conflicts with a bound sequence");
+
+        let mut state = self.lock();
+        let mut node = &state.root;
+        for stroke in strokes {
+            match node.child(stroke) {
+                Some(child) if child.binding.is_some() => return Err(conflict),
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        if state.node(strokes).is_some_and(|node| !node.children.is_empty()) {
+            return Err(conflict);
+        }
+
+        let id = SequenceId(state.next_id);
+        state.next_id += 1;
+        let mut node = &mut state.root;
+        for stroke in strokes {
+            let index = match node.children.iter().position(|(s, _)| s == stroke) {
+                Some(index) => index,
+                None => {
+                    node.children.push((*stroke, Node::default()));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index].1;
+        }
+        node.binding = Some((id, Arc::new(callback)));
+        Ok(id)
+    }
+
+    /// Remove a bound sequence.
+    ///
+    /// Returns `true` if the sequence was bound.
+    pub fn unbind(&self, id: SequenceId) -> bool {
+        let mut state = self.lock();
+        let removed = state.root.remove(id);
+        if removed && state.node(&state.pending).is_none() {
+            state.pending.clear();
+        }
+        removed
+    }
+
+    /// Set the callback told about every stroke that extends a sequence
+    /// without completing it, e.g. to show which strokes may follow.
+    pub fn on_partial_match<F>(&self, callback: F)
+    where
+        F: Fn(&PartialMatch) + Send + Sync + 'static,
+    {
+        self.lock().on_partial = Some(Arc::new(callback));
+    }
+
+    /// Set the callback told when a partially typed sequence is abandoned.
+    pub fn on_cancel<F>(&self, callback: F)
+    where
+        F: Fn(CancelReason) + Send + Sync + 'static,
+    {
+        self.lock().on_cancel = Some(Arc::new(callback));
+    }
+
+    /// Returns the strokes of the sequence typed so far.
+    pub fn pending(&self) -> Vec<Hotkey> {
+        self.lock().pending.clone()
+    }
+
+    /// Feed a keyboard event to the matcher.
+    ///
+    /// Only presses advance a sequence; releases are used to tell auto-repeated
+    /// presses, which are ignored, from new ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event, with the time and modifiers it occurred with.
+    ///
+    /// # Returns
+    ///
+    /// The id of the sequence the event completed, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uiohook_rs::hook::keyboard::{KeyCode, KeyboardEvent, KeyboardEventType};
+    /// use uiohook_rs::hook::modifiers::Modifiers;
+    /// use uiohook_rs::sequence::SequenceMatcher;
+    /// use uiohook_rs::EventMeta;
+    /// use std::time::Duration;
+    ///
+    /// let sequences = SequenceMatcher::new();
+    /// let save = sequences.bind("Ctrl+X Ctrl+S", |_| println!("Save")).expect("Failed to bind");
+    ///
+    /// let press = |key_code, ms| KeyboardEvent {
+    ///     event_type: KeyboardEventType::Pressed,
+    ///     key_code,
+    ///     raw_code: 0,
+    ///     key_char: None,
+    ///     meta: EventMeta::new(Duration::from_millis(ms), Modifiers::CTRL_L),
+    /// };
+    /// assert_eq!(sequences.feed(&press(KeyCode::X, 0)), None);
+    /// assert_eq!(sequences.feed(&press(KeyCode::S, 300)), Some(save));
+    /// ```
+    pub fn feed(&self, event: &KeyboardEvent) -> Option<SequenceId> {
+        let mut notifications = Vec::new();
+        let fired = self.advance(event, &mut notifications);
+        for notification in notifications {
+            match notification {
+                Notification::Fired(callback) => callback(event),
+                Notification::Partial(callback, partial) => callback(&partial),
+                Notification::Cancelled(callback, reason) => callback(reason),
+            }
+        }
+        fired
+    }
+
+    /// Abandon the pending sequence if its next stroke is overdue at `now`.
+    ///
+    /// Timeouts are otherwise only noticed when the next key is pressed; call
+    /// this from a timer to hide hints promptly. `now` is measured like event
+    /// times, as the time since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a pending sequence was abandoned.
+    pub fn expire(&self, now: Duration) -> bool {
+        let mut state = self.lock();
+        if state.pending.is_empty() || now.saturating_sub(state.last_stroke) <= state.timeout {
+            return false;
+        }
+        state.pending.clear();
+        let callback = state.on_cancel.clone();
+        drop(state);
+
+        if let Some(callback) = callback {
+            callback(CancelReason::Timeout);
+        }
+        true
+    }
+
+    /// Abandon the pending sequence without reporting it.
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.pending.clear();
+        state.held.clear();
+    }
+
+    fn advance(&self, event: &KeyboardEvent, notifications: &mut Vec<Notification>) -> Option<SequenceId> {
+        let mut state = self.lock();
+        match event.event_type {
+            KeyboardEventType::Pressed => {}
+            KeyboardEventType::Released => {
+                state.held.remove(&event.key_code);
+                return None;
+            }
+            KeyboardEventType::Typed => return None,
+        }
+        if !state.held.insert(event.key_code) || event.key_code.is_modifier() {
+            return None;
+        }
+
+        let cancel = |state: &mut MatcherState, reason, notifications: &mut Vec<Notification>| {
+            state.pending.clear();
+            if let Some(callback) = state.on_cancel.clone() {
+                notifications.push(Notification::Cancelled(callback, reason));
+            }
+        };
+
+        if !state.pending.is_empty() {
+            if event.key_code == CANCEL_KEY {
+                cancel(&mut state, CancelReason::Escape, notifications);
+                return None;
+            }
+            if event.meta.time.saturating_sub(state.last_stroke) > state.timeout {
+                cancel(&mut state, CancelReason::Timeout, notifications);
+            }
+        }
+
+        let mut step = state.node(&state.pending).and_then(|node| node.matching_child(event));
+        if step.is_none() && !state.pending.is_empty() {
+            // Start over, so the stroke may begin another sequence.
+            cancel(&mut state, CancelReason::Mismatch, notifications);
+            step = state.root.matching_child(event);
+        }
+        let (stroke, node) = step?;
+
+        if let Some((id, callback)) = node.binding.as_ref().map(|(id, callback)| (*id, callback.clone())) {
+            state.pending.clear();
+            notifications.push(Notification::Fired(callback));
+            return Some(id);
+        }
+
+        let next = node.children.iter().map(|(stroke, _)| *stroke).collect();
+        state.pending.push(stroke);
+        state.last_stroke = event.meta.time;
+        if let Some(callback) = state.on_partial.clone() {
+            let partial = PartialMatch {
+                strokes: state.pending.clone(),
+                next,
+            };
+            notifications.push(Notification::Partial(callback, partial));
+        }
+        None
+    }
+}
+
+impl Default for SequenceMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventHandler for SequenceMatcher {
+    fn handle_event(&self, event: &UiohookEvent) {
+        match event {
+            UiohookEvent::Keyboard(ke) => {
+                self.feed(ke);
+            }
+            // Releases are not delivered while the hook is down.
+            UiohookEvent::HookDisabled => self.reset(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hook::backend::{HookBackend, MockBackend};
+    use crate::hook::modifiers::Modifiers;
+    use crate::EventMeta;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn key_event(event_type: KeyboardEventType, key_code: KeyCode, modifiers: Modifiers, ms: u64) -> KeyboardEvent {
+        KeyboardEvent {
+            event_type,
+            key_code,
+            raw_code: 0,
+            key_char: None,
+            meta: EventMeta::new(Duration::from_millis(ms), modifiers),
+        }
+    }
+
+    // Feeds a press and release of `key_code`, returning what the press completed.
+    fn tap(matcher: &SequenceMatcher, key_code: KeyCode, modifiers: Modifiers, ms: u64) -> Option<SequenceId> {
+        let fired = matcher.feed(&key_event(KeyboardEventType::Pressed, key_code, modifiers, ms));
+        matcher.feed(&key_event(KeyboardEventType::Released, key_code, modifiers, ms + 10));
+        fired
+    }
+
+    fn record_cancels(matcher: &SequenceMatcher) -> Arc<Mutex<Vec<CancelReason>>> {
+        let cancels = Arc::new(Mutex::new(Vec::new()));
+        let recorded = cancels.clone();
+        matcher.on_cancel(move |reason| recorded.lock().unwrap().push(reason));
+        cancels
+    }
+
+    #[test]
+    fn test_sequence_with_held_modifier() {
+        let matcher = SequenceMatcher::new();
+        let save = matcher.bind("Ctrl+X Ctrl+S", |_| {}).unwrap();
+        let ctrl = Modifiers::CTRL_L;
+
+        assert_eq!(matcher.feed(&key_event(KeyboardEventType::Pressed, KeyCode::ControlL, ctrl, 0)), None);
+        assert_eq!(tap(&matcher, KeyCode::X, ctrl, 100), None);
+        assert_eq!(matcher.pending(), vec!["Ctrl+X".parse().unwrap()]);
+        assert_eq!(tap(&matcher, KeyCode::S, ctrl, 200), Some(save));
+        assert!(matcher.pending().is_empty());
+
+        // Without Ctrl the second stroke does not match.
+        assert_eq!(tap(&matcher, KeyCode::X, ctrl, 300), None);
+        assert_eq!(tap(&matcher, KeyCode::S, Modifiers::empty(), 400), None);
+        assert!(matcher.pending().is_empty());
+    }
+
+    #[test]
+    fn test_leader_sequences() {
+        let matcher = SequenceMatcher::new();
+        assert_eq!(matcher.bind("leader g", |_| {}), Err(UiohookError::InvalidArgument("no leader key set")));
+
+        matcher.set_leader("Space").unwrap();
+        let definition = matcher.bind("leader g d", |_| {}).unwrap();
+        let references = matcher.bind("LEADER g r", |_| {}).unwrap();
+        let none = Modifiers::empty();
+
+        assert_eq!(tap(&matcher, KeyCode::Space, none, 0), None);
+        assert_eq!(tap(&matcher, KeyCode::G, none, 100), None);
+        assert_eq!(tap(&matcher, KeyCode::D, none, 200), Some(definition));
+        assert_eq!(tap(&matcher, KeyCode::Space, none, 300), None);
+        assert_eq!(tap(&matcher, KeyCode::G, none, 400), None);
+        assert_eq!(tap(&matcher, KeyCode::R, none, 500), Some(references));
+    }
+
+    #[test]
+    fn test_prefix_conflicts() {
+        let matcher = SequenceMatcher::new();
+        let conflict = Err(UiohookError::InvalidArgument("sequence conflicts with a bound sequence"));
+        matcher.bind("Ctrl+X Ctrl+S", |_| {}).unwrap();
+
+        assert_eq!(matcher.bind("Ctrl+X", |_| {}), conflict);
+        assert_eq!(matcher.bind("Ctrl+X Ctrl+S", |_| {}), conflict);
+        assert_eq!(matcher.bind("Ctrl+X Ctrl+S Q", |_| {}), conflict);
+        assert!(matcher.bind("Ctrl+X Ctrl+F", |_| {}).is_ok());
+        assert!(matcher.bind("Ctrl+C", |_| {}).is_ok());
+        assert_eq!(matcher.bind("", |_| {}), Err(UiohookError::InvalidArgument("sequence has no strokes")));
+        assert_eq!(matcher.bind("Ctrl+Nope", |_| {}), Err(UiohookError::InvalidArgument("unknown key name")));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let matcher = SequenceMatcher::new();
+        matcher.set_timeout(Duration::from_millis(500));
+        let cancels = record_cancels(&matcher);
+        let id = matcher.bind("g g", |_| {}).unwrap();
+        let none = Modifiers::empty();
+
+        assert_eq!(tap(&matcher, KeyCode::G, none, 1000), None);
+        // Too late: the press starts the sequence over instead.
+        assert_eq!(tap(&matcher, KeyCode::G, none, 1501), None);
+        assert_eq!(*cancels.lock().unwrap(), vec![CancelReason::Timeout]);
+        assert_eq!(tap(&matcher, KeyCode::G, none, 2001), Some(id));
+
+        assert_eq!(tap(&matcher, KeyCode::G, none, 3000), None);
+        assert!(!matcher.expire(Duration::from_millis(3500)));
+        assert!(matcher.expire(Duration::from_millis(3501)));
+        assert!(matcher.pending().is_empty());
+        assert_eq!(cancels.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_escape_and_mismatch_cancel() {
+        let matcher = SequenceMatcher::new();
+        let cancels = record_cancels(&matcher);
+        let partials = Arc::new(Mutex::new(Vec::new()));
+        let recorded = partials.clone();
+        matcher.on_partial_match(move |partial| recorded.lock().unwrap().push(partial.clone()));
+        matcher.bind("Ctrl+X Ctrl+S", |_| {}).unwrap();
+        matcher.bind("Ctrl+X Ctrl+F", |_| {}).unwrap();
+        let quit = matcher.bind("Q", |_| {}).unwrap();
+        let ctrl = Modifiers::CTRL_R;
+
+        tap(&matcher, KeyCode::X, ctrl, 0);
+        assert_eq!(
+            *partials.lock().unwrap(),
+            vec![PartialMatch {
+                strokes: vec!["Ctrl+X".parse().unwrap()],
+                next: vec!["Ctrl+S".parse().unwrap(), "Ctrl+F".parse().unwrap()],
+            }]
+        );
+        assert_eq!(tap(&matcher, KeyCode::Escape, Modifiers::empty(), 100), None);
+        assert!(matcher.pending().is_empty());
+
+        // A stroke that continues nothing cancels and may start a new sequence.
+        tap(&matcher, KeyCode::X, ctrl, 200);
+        assert_eq!(tap(&matcher, KeyCode::Q, Modifiers::empty(), 300), Some(quit));
+        assert_eq!(*cancels.lock().unwrap(), vec![CancelReason::Escape, CancelReason::Mismatch]);
+
+        // Escape outside a sequence is an ordinary stroke.
+        assert_eq!(tap(&matcher, KeyCode::Escape, Modifiers::empty(), 400), None);
+        assert_eq!(cancels.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_auto_repeat_is_ignored() {
+        let matcher = SequenceMatcher::new();
+        let id = matcher.bind("g g", |_| {}).unwrap();
+        let none = Modifiers::empty();
+
+        assert_eq!(matcher.feed(&key_event(KeyboardEventType::Pressed, KeyCode::G, none, 0)), None);
+        assert_eq!(matcher.feed(&key_event(KeyboardEventType::Pressed, KeyCode::G, none, 30)), None);
+        matcher.feed(&key_event(KeyboardEventType::Released, KeyCode::G, none, 60));
+        assert_eq!(matcher.feed(&key_event(KeyboardEventType::Pressed, KeyCode::G, none, 90)), Some(id));
+    }
+
+    #[test]
+    fn test_unbind() {
+        let matcher = SequenceMatcher::new();
+        let save = matcher.bind("Ctrl+X Ctrl+S", |_| {}).unwrap();
+        tap(&matcher, KeyCode::X, Modifiers::CTRL_L, 0);
+
+        assert!(matcher.unbind(save));
+        assert!(!matcher.unbind(save));
+        assert!(matcher.pending().is_empty());
+        // The pruned branch no longer blocks shorter sequences.
+        assert!(matcher.bind("Ctrl+X", |_| {}).is_ok());
+    }
+
+    #[test]
+    fn test_injected_events() {
+        let backend = MockBackend::new();
+        let matcher = SequenceMatcher::new();
+        backend.dispatcher().subscribe(matcher.clone());
+        let count = Arc::new(AtomicUsize::new(0));
+        let fired = count.clone();
+        matcher
+            .bind("Ctrl+K Ctrl+C", move |_| {
+                fired.fetch_add(1, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        for (event_type, key_code, ms) in [
+            (KeyboardEventType::Pressed, KeyCode::ControlL, 0),
+            (KeyboardEventType::Pressed, KeyCode::K, 50),
+            (KeyboardEventType::Released, KeyCode::K, 80),
+            (KeyboardEventType::Pressed, KeyCode::C, 120),
+            (KeyboardEventType::Released, KeyCode::C, 150),
+            (KeyboardEventType::Released, KeyCode::ControlL, 200),
+        ] {
+            let event = key_event(event_type, key_code, Modifiers::CTRL_L, ms);
+            backend.inject(&UiohookEvent::Keyboard(event));
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -247,6 +247,60 @@ pub fn get_multi_click_time() -> Result<i64> {
     }
 }
 
+/// The multi-click time, in milliseconds, to assume when the system does not
+/// report one.
+pub const DEFAULT_MULTI_CLICK_TIME: i64 = 500;
+
+/// Keyboard and pointer settings reported by the operating system.
+///
+/// Each setting is `None` if the system does not report it, which is common
+/// for the pointer acceleration and auto repeat settings on X11 and Wayland.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemProperties {
+    /// The keyboard auto repeat rate.
+    pub auto_repeat_rate: Option<i64>,
+    /// The keyboard auto repeat delay in milliseconds.
+    pub auto_repeat_delay: Option<i64>,
+    /// The pointer acceleration multiplier.
+    pub pointer_acceleration_multiplier: Option<i64>,
+    /// The pointer acceleration threshold.
+    pub pointer_acceleration_threshold: Option<i64>,
+    /// The pointer sensitivity.
+    pub pointer_sensitivity: Option<i64>,
+    /// The maximum time in milliseconds between clicks of a multi-click.
+    pub multi_click_time: Option<i64>,
+}
+
+/// Retrieves all keyboard and pointer settings at once.
+///
+/// A setting that cannot be read is left as `None` rather than hiding the
+/// settings that could.
+///
+/// # Returns
+///
+/// The `SystemProperties` of the current system.
+///
+/// # Examples
+///
+/// ```
+/// use uiohook_rs::utils::system_properties;
+///
+/// match system_properties().multi_click_time {
+///     Some(time) => println!("Multi-click time: {} ms", time),
+///     None => eprintln!("The multi-click time is not available"),
+/// }
+/// ```
+pub fn system_properties() -> SystemProperties {
+    SystemProperties {
+        auto_repeat_rate: get_auto_repeat_rate().ok(),
+        auto_repeat_delay: get_auto_repeat_delay().ok(),
+        pointer_acceleration_multiplier: get_pointer_acceleration_multiplier().ok(),
+        pointer_acceleration_threshold: get_pointer_acceleration_threshold().ok(),
+        pointer_sensitivity: get_pointer_sensitivity().ok(),
+        multi_click_time: get_multi_click_time().ok(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;